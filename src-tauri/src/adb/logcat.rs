@@ -1,28 +1,128 @@
 use std::collections::HashMap;
-use std::fs::OpenOptions;
-use std::io::{BufRead, BufReader, Write};
-use std::process::{Child, Command, Stdio};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
-use std::thread;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Duration;
+
+use async_channel::{bounded, Receiver, Sender};
+use async_process::{Command, Stdio};
+use async_io::Timer;
+use futures_lite::{io::BufReader, AsyncBufReadExt, FutureExt, StreamExt};
+use regex::Regex;
 use tauri::State;
 
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
 
-// Structure to hold the process and the shared buffer
+// How often we re-check a filtered app's PID while logcat is streaming, so an app
+// restart (new PID) is noticed without polling try_wait() on a timer.
+const PID_CHECK_INTERVAL: Duration = Duration::from_millis(1500);
+// How long to wait before retrying after the app isn't running yet, or adb itself
+// failed to spawn.
+const RETRY_DELAY: Duration = Duration::from_millis(1500);
+// Grace period given to a SIGTERM'd/CTRL'd adb logcat to exit on its own before an
+// internal restart (PID change) escalates to a hard kill. `stop_logcat`'s caller can
+// override this for the user-initiated stop via its `grace_period_secs` parameter.
+const DEFAULT_TERMINATE_GRACE: Duration = Duration::from_secs(2);
+
+/// One monitored device's logcat stream. The `adb logcat` child and its reader live
+/// entirely inside the supervisor task spawned by `start_logcat`; all we keep here is
+/// the shared output buffer plus a channel the stop path uses to signal it.
 pub struct LogcatProcess {
-    // Child is now optional and protected by Mutex to allow replacement/restarting
-    child: Arc<Mutex<Option<Child>>>,
-    // Flag to signal the monitoring thread to stop
-    should_stop: Arc<AtomicBool>,
-    buffer: Arc<Mutex<Vec<String>>>,
+    // Sending the desired grace period on this tells the supervisor task to stop
+    // gracefully at its next checkpoint.
+    stop_tx: Sender<Duration>,
+    buffer: Arc<Mutex<RingBuffer>>,
     output_file: Option<String>,
 }
 
 pub struct LogcatState(pub Mutex<HashMap<String, LogcatProcess>>);
 
+/// A single parsed `-v threadtime` line, produced once as the line arrives so neither
+/// `fetch_logcat_entries` nor the frontend has to re-parse it on every poll. `raw` is
+/// kept alongside the parsed fields so `fetch_logcat_buffer` can keep serving verbatim
+/// text, and so lines that don't match the threadtime shape (multiline stack traces,
+/// our own "--- Logcat started/stopped ---" markers) still show up with an empty
+/// timestamp/pid/tid/level/tag rather than being dropped.
+#[derive(Clone, serde::Serialize)]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub pid: String,
+    pub tid: String,
+    pub level: String,
+    pub tag: String,
+    pub message: String,
+    pub raw: String,
+}
+
+fn threadtime_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"^(\d{2}-\d{2} \d{2}:\d{2}:\d{2}\.\d{3})\s+(\d+)\s+(\d+)\s+([VDIWEF])\s+([^:]*):\s?(.*)$")
+            .unwrap()
+    })
+}
+
+fn parse_log_line(line: &str) -> LogEntry {
+    if let Some(caps) = threadtime_regex().captures(line) {
+        return LogEntry {
+            timestamp: caps[1].to_string(),
+            pid: caps[2].to_string(),
+            tid: caps[3].to_string(),
+            level: caps[4].to_string(),
+            tag: caps[5].trim().to_string(),
+            message: caps[6].to_string(),
+            raw: line.to_string(),
+        };
+    }
+
+    LogEntry {
+        timestamp: String::new(),
+        pid: String::new(),
+        tid: String::new(),
+        level: String::new(),
+        tag: String::new(),
+        message: line.to_string(),
+        raw: line.to_string(),
+    }
+}
+
+// Once the buffer passes BUFFER_CAP entries, the oldest TRIM_CHUNK are dropped: the
+// same 10k threshold and 1000-entry trim the old `Vec<String>` ring used.
+const BUFFER_CAP: usize = 10000;
+const TRIM_CHUNK: usize = 1000;
+
+/// The in-memory log ring, now storing parsed entries instead of raw strings. `trimmed`
+/// counts how many entries have ever fallen off the front, so offsets reported to
+/// clients stay meaningful (monotonically increasing against the full stream) even
+/// though `entries` itself only holds the most recent slice.
+#[derive(Default)]
+struct RingBuffer {
+    entries: Vec<LogEntry>,
+    trimmed: usize,
+}
+
+impl RingBuffer {
+    fn push(&mut self, line: String) {
+        self.entries.push(parse_log_line(&line));
+        if self.entries.len() > BUFFER_CAP {
+            self.entries.drain(0..TRIM_CHUNK);
+            self.trimmed += TRIM_CHUNK;
+        }
+    }
+
+    fn total_len(&self) -> usize {
+        self.trimmed + self.entries.len()
+    }
+
+    // Maps a client-reported offset (against `total_len()`) to an index into
+    // `entries`, clamping up to 0 when the requested range has already been trimmed
+    // away so the client just gets everything still available instead of a panic.
+    fn local_offset(&self, offset: usize) -> usize {
+        offset.saturating_sub(self.trimmed).min(self.entries.len())
+    }
+}
+
 #[tauri::command]
 pub fn start_logcat(
     state: State<'_, LogcatState>,
@@ -31,282 +131,315 @@ pub fn start_logcat(
     level: Option<String>,
     output_file: Option<String>,
 ) -> Result<String, String> {
-    let mut procs = state.0.lock().map_err(|_e| _e.to_string())?;
+    let mut procs = state.0.lock().map_err(|e| e.to_string())?;
 
     if procs.contains_key(&device) {
         return Ok("Logcat already running".to_string());
     }
 
-    // Shared State for the supervisor thread
-    let buffer = Arc::new(Mutex::new(Vec::new()));
-    match output_file.clone() {
-        Some(path) => {
-             // Add header to buffer
-             if let Ok(mut b) = buffer.lock() {
-                 b.push(format!("--- Logcat started for device: {} (Writing to {}) ---", device, path));
-             }
-        },
-        None => {
-            if let Ok(mut b) = buffer.lock() {
-                b.push(format!("--- Logcat started for device: {} ---", device));
+    let buffer = Arc::new(Mutex::new(RingBuffer::default()));
+    let header = match &output_file {
+        Some(path) => format!("--- Logcat started for device: {} (Writing to {}) ---", device, path),
+        None => format!("--- Logcat started for device: {} ---", device),
+    };
+    if let Ok(mut b) = buffer.lock() {
+        b.push(header);
+    }
+
+    // Capacity 1: only one stop signal is ever meaningful, and stop_logcat shouldn't
+    // block if the supervisor hasn't reached a checkpoint yet.
+    let (stop_tx, stop_rx) = bounded(1);
+
+    tauri::async_runtime::spawn(supervise_device(
+        device.clone(),
+        filter,
+        level.unwrap_or_else(|| "V".to_string()),
+        buffer.clone(),
+        output_file.clone(),
+        stop_rx,
+    ));
+
+    procs.insert(device, LogcatProcess { stop_tx, buffer, output_file });
+
+    Ok("Logcat started".to_string())
+}
+
+/// What ended the current `adb logcat` child, so the supervisor knows whether to
+/// respawn or stop for good.
+enum StreamOutcome {
+    Stopped(Duration),
+    ChildExited,
+    PidChanged,
+}
+
+/// Outcome of a "wait for the retry delay, unless stop fires first" race.
+enum WaitOutcome {
+    TimedOut,
+    Stop(Duration),
+}
+
+/// Owns one device's `adb logcat` lifecycle on the shared async executor: spawn,
+/// stream lines into the buffer, and restart on exit or a filtered app's PID change,
+/// until `stop_rx` fires. Replaces the old supervisor-thread + reader-thread +
+/// 1-second `try_wait()` poll with a single task that reacts the instant any of those
+/// events happens.
+async fn supervise_device(
+    device_id: String,
+    pkg: Option<String>,
+    lvl: String,
+    buffer: Arc<Mutex<RingBuffer>>,
+    output_file: Option<String>,
+    stop_rx: Receiver<Duration>,
+) {
+    loop {
+        if stop_rx.try_recv().is_ok() {
+            finish(&buffer, &output_file, None).await;
+            return;
+        }
+
+        // 1. Resolve PID if a package filter was requested.
+        let mut current_pid: Option<String> = None;
+        if let Some(ref package) = pkg {
+            current_pid = get_pid(&device_id, package).await.ok().flatten();
+            if current_pid.is_none() {
+                // App not running yet: wait and retry, but stay responsive to stop.
+                match wait_or_stop(&stop_rx).await {
+                    WaitOutcome::TimedOut => continue,
+                    WaitOutcome::Stop(_) => {
+                        finish(&buffer, &output_file, None).await;
+                        return;
+                    }
+                }
             }
         }
-    }
 
-    let child_mutex = Arc::new(Mutex::new(None));
-    let should_stop = Arc::new(AtomicBool::new(false));
-
-    // Clones for the thread
-    let thread_device = device.clone();
-    let thread_filter = filter.clone();
-    let thread_level = level.clone();
-    let thread_buffer = buffer.clone();
-    let thread_output_file = output_file.clone();
-    let thread_child_mutex = child_mutex.clone();
-    let thread_should_stop = should_stop.clone();
-
-    thread::spawn(move || {
-        let device_id = thread_device;
-        let pkg = thread_filter;
-        let lvl = thread_level.unwrap_or_else(|| "V".to_string()); // Default to Verbose but we format later
-
-        // Loop until stopped
-        while !thread_should_stop.load(Ordering::Relaxed) {
-            let mut current_pid: Option<String> = None;
-
-            // 1. Resolve PID if package is provided
-            if let Some(ref package) = pkg {
-                // Try to find PID
-                match get_pid(&device_id, package) {
-                     Ok(Some(pid)) => {
-                         current_pid = Some(pid);
-                     },
-                     Ok(None) => {
-                         // App not running, wait and retry
-                         // println!("Logcat: App {} not running, waiting...", package);
-                     },
-                     Err(_) => {
-                         // Error checking
-                     }
+        // 2. Spawn the child for this PID/filter.
+        let mut child = match spawn_logcat_child(&device_id, current_pid.as_deref(), &lvl) {
+            Ok(c) => c,
+            Err(_) => {
+                match wait_or_stop(&stop_rx).await {
+                    WaitOutcome::TimedOut => continue,
+                    WaitOutcome::Stop(_) => {
+                        finish(&buffer, &output_file, None).await;
+                        return;
+                    }
+                }
+            }
+        };
+        let pid = child.id();
+        let stdout = child.stdout.take();
+
+        // Reader: stream lines into the buffer (and the output file) until EOF. The
+        // file handle is shared so the stop path can fsync and append a trailer to
+        // the exact writer that captured the run, rather than reopening the path.
+        let file_writer: Arc<Mutex<Option<File>>> = Arc::new(Mutex::new(
+            output_file.as_ref().and_then(|path| OpenOptions::new().create(true).append(true).open(path).ok()),
+        ));
+        let reader_buffer = buffer.clone();
+        let reader_file_writer = file_writer.clone();
+        let read_to_eof = async move {
+            if let Some(out) = stdout {
+                let mut lines = BufReader::new(out).lines();
+                while let Some(Ok(line)) = lines.next().await {
+                    if let Ok(mut guard) = reader_file_writer.lock() {
+                        if let Some(f) = guard.as_mut() {
+                            let _ = writeln!(f, "{}", line);
+                        }
+                    }
+                    if let Ok(mut b) = reader_buffer.lock() {
+                        b.push(line);
+                    }
+                }
+            }
+        };
+
+        // Periodic PID re-check, only meaningful when filtering by package. With no
+        // filter this simply never resolves, so it never wins the race below.
+        let watch_pid = async {
+            loop {
+                Timer::after(PID_CHECK_INTERVAL).await;
+                let Some(ref package) = pkg else { continue };
+                let Some(ref old_pid) = current_pid else { continue };
+                match get_pid(&device_id, package).await {
+                    Ok(Some(new_pid)) if new_pid != *old_pid => return,
+                    Ok(None) => return, // app died; kill and go back to searching
+                    _ => {}
                 }
             }
-            
-            // If we have a package filter but no PID, wait and continue
-            if pkg.is_some() && current_pid.is_none() {
-                 if thread_should_stop.load(Ordering::Relaxed) { break; }
-                 thread::sleep(Duration::from_millis(1500));
-                 continue;
+        };
+
+        let outcome = async { read_to_eof.await; StreamOutcome::ChildExited }
+            .or(async { let _ = child.status().await; StreamOutcome::ChildExited })
+            .or(async { watch_pid.await; StreamOutcome::PidChanged })
+            .or(async { StreamOutcome::Stopped(stop_rx.recv().await.unwrap_or(DEFAULT_TERMINATE_GRACE)) })
+            .await;
+
+        match outcome {
+            StreamOutcome::Stopped(grace) => {
+                // User-requested stop: terminate gently before touching the files.
+                graceful_kill(&mut child, grace).await;
+                finish(&buffer, &output_file, Some(&file_writer)).await;
+                return;
             }
-
-            // 2. Start Logcat Process
-            let mut args = vec!["-s", &device_id, "shell", "logcat"];
-            if let Some(ref p) = current_pid {
-                args.push("--pid");
-                args.push(p);
+            StreamOutcome::PidChanged => {
+                // Internal restart: same courtesy, just with the default grace period.
+                graceful_kill(&mut child, DEFAULT_TERMINATE_GRACE).await;
             }
-            args.push("-v");
-            args.push("threadtime");
-            
-            let level_arg = format!("*:{}", lvl);
-            args.push(&level_arg);
-
-            // Spawn
-            let mut cmd = Command::new("adb");
-            cmd.args(&args);
-            cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
-            #[cfg(target_os = "windows")]
-            cmd.creation_flags(0x08000000); 
-
-            match cmd.spawn() {
-                Ok(mut child_proc) => {
-                     // println!("Logcat: Started process for PID {:?}", current_pid);
-                     let stdout = child_proc.stdout.take();
-                     
-                     // Store child
-                     {
-                         let mut lock = thread_child_mutex.lock().unwrap();
-                         *lock = Some(child_proc);
-                     }
-
-                     // SPAWN READER THREAD
-                     // We need a separate thread because reader.lines() blocks
-                     if let Some(out) = stdout {
-                         let reader_buffer = thread_buffer.clone();
-                         let reader_output_file = thread_output_file.clone();
-                         let reader_should_stop = thread_should_stop.clone();
-                         
-                         thread::spawn(move || {
-                             let reader = BufReader::new(out);
-                             let mut file_writer = if let Some(ref path) = reader_output_file {
-                                OpenOptions::new().create(true).append(true).open(path).ok()
-                             } else { None };
-
-                             for line in reader.lines() {
-                                 // Stop reading if global stop is requested
-                                 if reader_should_stop.load(Ordering::Relaxed) { break; }
-                                 
-                                 if let Ok(l) = line {
-                                     // Write file
-                                     if let Some(ref mut f) = file_writer {
-                                         let _ = writeln!(f, "{}", l);
-                                     }
-                                     // Buffer
-                                     if let Ok(mut b) = reader_buffer.lock() {
-                                         b.push(l);
-                                         if b.len() > 10000 {
-                                             b.drain(0..1000);
-                                         }
-                                     }
-                                 } else {
-                                     break; // Stream broken or process killed
-                                 }
-                             }
-                         });
-                     }
-                     
-                     // MONITOR LOOP
-                     // Watch the child and the App PID
-                     loop {
-                         if thread_should_stop.load(Ordering::Relaxed) { break; }
-                         thread::sleep(Duration::from_millis(1000));
-
-                         // 1. Check if child is still running
-                         let mut child_dead = false;
-                         {
-                             let mut lock = thread_child_mutex.lock().unwrap();
-                             if let Some(child) = lock.as_mut() {
-                                 match child.try_wait() {
-                                     Ok(Some(_)) => child_dead = true, // Exited naturally
-                                     Ok(None) => {}, // Still running
-                                     Err(_) => child_dead = true,
-                                 }
-                             } else {
-                                 child_dead = true; // No child?
-                             }
-                         }
-
-                         if child_dead {
-                             // println!("Logcat: Child exited naturally or error.");
-                             break; // Go back to start of supervisor loop to restart
-                         }
-
-                         // 2. Check if App PID changed (Only if we are filtering by package)
-                         if let Some(ref package) = pkg {
-                              // If we knew a PID, check if it's stillvalid
-                              if let Some(ref old_pid) = current_pid {
-                                  match get_pid(&device_id, package) {
-                                      Ok(Some(new_pid)) => {
-                                          if new_pid != *old_pid {
-                                              // PID Changed! App restarted.
-                                              // println!("Logcat: PID changed from {} to {}. Restarting...", old_pid, new_pid);
-                                              
-                                              // Kill current child to force restart
-                                              let mut lock = thread_child_mutex.lock().unwrap();
-                                              if let Some(mut child) = lock.take() {
-                                                  let _ = child.kill();
-                                              }
-                                              break; // Monitor loop ends -> Supervisor loop restarts
-                                          }
-                                      },
-                                      Ok(None) => {
-                                          // App died (returns None). 
-                                          // Keep waiting? Or kill logcat?
-                                          // If app died, logcat --pid might stay alive waiting.
-                                          // Better to kill and go back to searching.
-                                          // println!("Logcat: App died. Killing logcat waiting for restart.");
-                                          let mut lock = thread_child_mutex.lock().unwrap();
-                                          if let Some(mut child) = lock.take() {
-                                              let _ = child.kill();
-                                          }
-                                          break;
-                                      },
-                                      Err(_) => {}
-                                  }
-                              }
-                         }
-                     }
-                     
-                     // Cleanup child handle (ensure it's cleared if we broke out)
-                     {
-                         let mut lock = thread_child_mutex.lock().unwrap();
-                         *lock = None;
-                     }
-                },
-                Err(_e) => {
-                    // println!("Logcat: Failed to spawn adb: {}", e);
-                    thread::sleep(Duration::from_secs(2));
+            StreamOutcome::ChildExited => {
+                // Already gone; reap without signaling.
+                let _ = child.status().await;
+
+                // Natural exit (device unplugged, adb server down, ...) with no PID
+                // change driving the restart: back off before respawning so a child
+                // that exits instantly doesn't hammer adb in a tight loop.
+                match wait_or_stop(&stop_rx).await {
+                    WaitOutcome::TimedOut => {}
+                    WaitOutcome::Stop(_) => {
+                        finish(&buffer, &output_file, None).await;
+                        return;
+                    }
                 }
             }
-            
-            // If we are just running global logcat (no filter), and it exits, we probably shouldn't restart immediately loop hard, 
-            // but `adb logcat` usually runs forever. If it crashes, restart is fine.
-            if pkg.is_none() {
-                 if thread_should_stop.load(Ordering::Relaxed) { break; }
-                 thread::sleep(Duration::from_secs(1));
+        }
+    }
+}
+
+/// Race the retry delay against a stop signal while there's no child to watch yet
+/// (waiting on the filtered app to launch, or backing off after a failed spawn).
+async fn wait_or_stop(stop_rx: &Receiver<Duration>) -> WaitOutcome {
+    Timer::after(RETRY_DELAY)
+        .map(|_| WaitOutcome::TimedOut)
+        .or(async { WaitOutcome::Stop(stop_rx.recv().await.unwrap_or(DEFAULT_TERMINATE_GRACE)) })
+        .await
+}
+
+/// Flush/fsync the run's output file and append a clear trailer to both it and the
+/// in-memory buffer, so a stopped capture is always well-terminated on disk. `writer`
+/// is `Some` when the stop happened mid-stream (reuses that file handle); `None` when
+/// stopping between spawns, where there is nothing open to fsync.
+async fn finish(
+    buffer: &Arc<Mutex<RingBuffer>>,
+    output_file: &Option<String>,
+    writer: Option<&Arc<Mutex<Option<File>>>>,
+) {
+    let trailer = "--- Logcat stopped ---".to_string();
+
+    if let Some(writer) = writer {
+        if let Ok(mut guard) = writer.lock() {
+            if let Some(f) = guard.as_mut() {
+                let _ = writeln!(f, "{}", trailer);
+                let _ = f.sync_all();
             }
         }
-        // println!("Logcat: Supervisor thread exiting for {}", device_id);
-    });
+    } else if let Some(path) = output_file {
+        if let Ok(mut f) = OpenOptions::new().create(true).append(true).open(path) {
+            let _ = writeln!(f, "{}", trailer);
+            let _ = f.sync_all();
+        }
+    }
+
+    if let Ok(mut b) = buffer.lock() {
+        b.push(trailer);
+    }
+}
 
-    procs.insert(device, LogcatProcess { 
-        child: child_mutex, 
-        should_stop, 
-        buffer, 
-        output_file 
-    });
+/// Ask the logcat child to exit the way a terminal SIGTERM would — giving adb a chance
+/// to flush and close the device-side stream — and only escalate to an unconditional
+/// kill if it's still alive after `grace`.
+async fn graceful_kill(child: &mut async_process::Child, grace: Duration) {
+    terminate_pid(child.id());
 
-    Ok("Logcat started".to_string())
+    let exited = child.status().map(|_| true).or(Timer::after(grace).map(|_| false)).await;
+    if !exited {
+        let _ = child.kill();
+        let _ = child.status().await;
+    }
 }
 
-fn get_pid(device: &str, pkg: &str) -> Result<Option<String>, String> {
+/// The "please exit" half of the shutdown: SIGTERM on Unix, or on Windows a
+/// non-forceful `taskkill` (no `/F`); no console control event is sent.
+fn terminate_pid(pid: u32) {
+    #[cfg(not(target_os = "windows"))]
+    {
+        use nix::sys::signal::{self, Signal};
+        use nix::unistd::Pid;
+        let _ = signal::kill(Pid::from_raw(pid as i32), Signal::SIGTERM);
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        // No SIGTERM on Windows; ask taskkill to close the process without /F so it
+        // gets a WM_CLOSE-style chance to shut down before we escalate.
+        let _ = std::process::Command::new("taskkill")
+            .args(&["/T", "/PID", &pid.to_string()])
+            .creation_flags(0x08000000) // CREATE_NO_WINDOW
+            .output();
+    }
+}
+
+fn spawn_logcat_child(device: &str, pid: Option<&str>, level: &str) -> std::io::Result<async_process::Child> {
+    let mut args = vec!["-s".to_string(), device.to_string(), "shell".to_string(), "logcat".to_string()];
+    if let Some(p) = pid {
+        args.push("--pid".to_string());
+        args.push(p.to_string());
+    }
+    args.push("-v".to_string());
+    args.push("threadtime".to_string());
+    args.push(format!("*:{}", level));
+
+    let mut cmd = Command::new("adb");
+    cmd.args(&args);
+    cmd.stdout(Stdio::piped()).stderr(Stdio::null());
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(0x08000000);
+
+    cmd.spawn()
+}
+
+async fn get_pid(device: &str, pkg: &str) -> Result<Option<String>, String> {
     let mut pidof_cmd = Command::new("adb");
     pidof_cmd.args(&["-s", device, "shell", "pidof", "-s", pkg]);
     #[cfg(target_os = "windows")]
     pidof_cmd.creation_flags(0x08000000);
 
-    match pidof_cmd.output() {
-        Ok(output) => {
-            let pid = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if pid.is_empty() {
+    let output = pidof_cmd.output().await.map_err(|e| e.to_string())?;
+    let pid = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if pid.is_empty() {
+        return Ok(None);
+    }
+
+    // Check process state (zombie/cached check).
+    let mut oom_cmd = Command::new("adb");
+    oom_cmd.args(&["-s", device, "shell", "cat", &format!("/proc/{}/oom_score_adj", pid)]);
+    #[cfg(target_os = "windows")]
+    oom_cmd.creation_flags(0x08000000);
+
+    if let Ok(oom_output) = oom_cmd.output().await {
+        let score_str = String::from_utf8_lossy(&oom_output.stdout).trim().to_string();
+        if let Ok(score) = score_str.parse::<i32>() {
+            // 900+ is cached.
+            if score >= 900 {
                 return Ok(None);
             }
-            
-            // Check process state (zombie/cached check)
-            let mut oom_cmd = Command::new("adb");
-            oom_cmd.args(&["-s", device, "shell", "cat", &format!("/proc/{}/oom_score_adj", pid)]);
-            #[cfg(target_os = "windows")]
-            oom_cmd.creation_flags(0x08000000);
-
-            if let Ok(oom_output) = oom_cmd.output() {
-                let score_str = String::from_utf8_lossy(&oom_output.stdout).trim().to_string();
-                if let Ok(score) = score_str.parse::<i32>() {
-                    // 900+ is cached
-                    if score >= 900 {
-                        return Ok(None);
-                    }
-                }
-            }
-            Ok(Some(pid))
-        },
-        Err(e) => Err(e.to_string())
+        }
     }
+    Ok(Some(pid))
 }
 
 #[tauri::command]
-pub fn stop_logcat(state: State<'_, LogcatState>, device: String) -> Result<String, String> {
+pub fn stop_logcat(
+    state: State<'_, LogcatState>,
+    device: String,
+    grace_period_secs: Option<u64>,
+) -> Result<String, String> {
     let mut procs = state.0.lock().map_err(|e| e.to_string())?;
 
     if let Some(process) = procs.remove(&device) {
-        // Signal stop
-        process.should_stop.store(true, Ordering::Relaxed);
-
-        // Kill current child if exists
-        let mut child_lock = process.child.lock().map_err(|e| e.to_string())?;
-        if let Some(mut child) = child_lock.take() {
-            let _ = child.kill();
-        }
-        
-        return Ok("Logcat stopped".to_string());
+        let grace = Duration::from_secs(grace_period_secs.unwrap_or(2));
+        // The supervisor task is parked in its `.or()` race, so this wakes it at the
+        // next poll instead of it waiting out a timer.
+        let _ = process.stop_tx.try_send(grace);
+        return Ok("Logcat stopping".to_string());
     }
 
     Ok("Logcat not running".to_string())
@@ -330,7 +463,7 @@ pub fn get_logcat_details(
     device: String,
 ) -> Result<LogcatDetails, String> {
     let procs = state.0.lock().map_err(|_e| _e.to_string())?;
-    
+
     if let Some(process) = procs.get(&device) {
         Ok(LogcatDetails {
             is_active: true,
@@ -354,15 +487,96 @@ pub fn fetch_logcat_buffer(
 
     if let Some(process) = procs.get(&device) {
         let buf = process.buffer.lock().map_err(|_e| _e.to_string())?;
-        
-        let len = buf.len();
-        if offset >= len {
-            return Ok((Vec::new(), len));
-        }
-        
-        let new_lines = buf[offset..].to_vec();
-        Ok((new_lines, len))
+        let local = buf.local_offset(offset);
+        let new_lines = buf.entries[local..].iter().map(|e| e.raw.clone()).collect();
+        Ok((new_lines, buf.total_len()))
     } else {
         Ok((Vec::new(), 0))
     }
 }
+
+// Ranks `V`/`D`/`I`/`W`/`E`/`F` for the `min_level` comparison; unrecognized strings
+// sort as `V` so a typo'd filter doesn't hide everything.
+fn level_rank(level: &str) -> u8 {
+    match level {
+        "V" => 0,
+        "D" => 1,
+        "I" => 2,
+        "W" => 3,
+        "E" => 4,
+        "F" => 5,
+        _ => 0,
+    }
+}
+
+/// Server-side filter for `fetch_logcat_entries`, so a UI watching for just `E`/`F`
+/// lines matching a crash pattern isn't shipped the other 10k lines per poll.
+#[derive(serde::Deserialize, Default)]
+pub struct LogcatFilter {
+    pub min_level: Option<String>,
+    pub tags_allow: Option<Vec<String>>,
+    pub tags_deny: Option<Vec<String>>,
+    pub message_regex: Option<String>,
+}
+
+impl LogcatFilter {
+    fn matches(&self, entry: &LogEntry, message_re: Option<&Regex>) -> bool {
+        if let Some(ref min) = self.min_level {
+            if !entry.level.is_empty() && level_rank(&entry.level) < level_rank(min) {
+                return false;
+            }
+        }
+        if let Some(ref allow) = self.tags_allow {
+            if !allow.is_empty() && !allow.iter().any(|t| t == &entry.tag) {
+                return false;
+            }
+        }
+        if let Some(ref deny) = self.tags_deny {
+            if deny.iter().any(|t| t == &entry.tag) {
+                return false;
+            }
+        }
+        if let Some(re) = message_re {
+            if !re.is_match(&entry.message) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[tauri::command]
+pub fn fetch_logcat_entries(
+    state: State<'_, LogcatState>,
+    device: String,
+    offset: usize,
+    filter: Option<LogcatFilter>,
+) -> Result<(Vec<LogEntry>, usize), String> {
+    let procs = state.0.lock().map_err(|_e| _e.to_string())?;
+
+    let Some(process) = procs.get(&device) else {
+        return Ok((Vec::new(), 0));
+    };
+
+    let buf = process.buffer.lock().map_err(|_e| _e.to_string())?;
+    let local = buf.local_offset(offset);
+    let total = buf.total_len();
+
+    // Compiled once per call rather than per entry; an invalid pattern is treated as
+    // "no message filter" instead of failing the whole fetch.
+    let message_re = filter
+        .as_ref()
+        .and_then(|f| f.message_regex.as_deref())
+        .and_then(|p| Regex::new(p).ok());
+
+    let entries = buf.entries[local..]
+        .iter()
+        .filter(|e| match &filter {
+            Some(f) => f.matches(e, message_re.as_ref()),
+            None => true,
+        })
+        .cloned()
+        .collect();
+
+    Ok((entries, total))
+}
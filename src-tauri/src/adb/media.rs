@@ -3,6 +3,48 @@ use std::fs::File;
 use std::io::Write;
 use std::thread;
 use std::time::Duration;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+// Android's screenrecord self-terminates at ~3 minutes, so we chain fixed-length
+// segments and stitch them back together on stop.
+const SEGMENT_SECONDS: u32 = 170;
+
+// How long to back off after a segment launch fails (device unplugged, adb down,
+// screenrecord unsupported) before retrying, so a persistently failing device doesn't
+// spin the worker thread at full speed.
+const SEGMENT_RETRY_DELAY: Duration = Duration::from_millis(1500);
+// Bail out of the relaunch loop after this many consecutive immediate failures instead
+// of retrying forever with nothing to show for it.
+const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+
+/// Live recording for a single device: a background worker keeps relaunching
+/// screenrecord into numbered segment files until stop is requested.
+pub struct RecordingSession {
+    should_stop: Arc<AtomicBool>,
+    // Remote segment paths on the device, in capture order.
+    segments: Arc<Mutex<Vec<String>>>,
+    worker: Option<thread::JoinHandle<()>>,
+}
+
+pub struct RecordingState(pub Mutex<HashMap<String, RecordingSession>>);
+
+impl Default for RecordingState {
+    fn default() -> Self {
+        Self(Mutex::new(HashMap::new()))
+    }
+}
+
+// Turn a device serial (which may contain ':' / '.' for wireless devices) into a
+// filename-safe stem for the on-device segment files.
+fn safe_stem(device: &str) -> String {
+    device
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
 
 #[tauri::command]
 pub async fn save_screenshot(device: String, path: String) -> Result<String, String> {
@@ -23,65 +65,166 @@ pub async fn save_screenshot(device: String, path: String) -> Result<String, Str
     Ok(path)
 }
 
-#[tauri::command]
-pub async fn start_screen_recording(device: String) -> Result<String, String> {
-    // Start screenrecord in background
-    // We use /sdcard/robot_runner_rec.mp4 as a temp file
-    // "screenrecord" typically runs until 3 mins or SIGINT.
-    
-    // We spawn it detached.
-    // Note: On Windows, pure spawn might leave a console window?
-    // We'll use the same trick as Scrcpy or creation flags if needed.
-    
+fn adb_shell(device: &str, args: &[&str]) -> std::io::Result<std::process::Output> {
+    let mut full = vec!["-s", device, "shell"];
+    full.extend_from_slice(args);
     let mut cmd = Command::new("adb");
-    cmd.args(&["-s", &device, "shell", "screenrecord", "--verbose", "/sdcard/robot_runner_rec.mp4"]);
-    
+    cmd.args(&full);
     #[cfg(target_os = "windows")]
     {
         use std::os::windows::process::CommandExt;
         cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
     }
+    cmd.output()
+}
+
+#[tauri::command]
+pub async fn start_screen_recording(state: State<'_, RecordingState>, device: String) -> Result<String, String> {
+    {
+        let procs = state.0.lock().map_err(|e| e.to_string())?;
+        if procs.contains_key(&device) {
+            return Ok("Recording already running".to_string());
+        }
+    }
+
+    let should_stop = Arc::new(AtomicBool::new(false));
+    let segments: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+    let stem = safe_stem(&device);
 
-    cmd.spawn().map_err(|e| format!("Failed to start recording: {}", e))?;
+    // Worker: relaunch screenrecord in chained segments until stop is requested, so
+    // the recording survives past Android's ~3 minute cap. Each segment is a blocking
+    // adb call that returns when the cap is hit (or when stop interrupts it).
+    let worker_stop = should_stop.clone();
+    let worker_segments = segments.clone();
+    let worker_device = device.clone();
+    let worker = thread::spawn(move || {
+        let mut index = 0u32;
+        let mut consecutive_failures = 0u32;
+        while !worker_stop.load(Ordering::Relaxed) {
+            let remote = format!("/sdcard/robot_runner_rec_{}_{}.mp4", stem, index);
+            let time_limit = SEGMENT_SECONDS.to_string();
+            let result = adb_shell(
+                &worker_device,
+                &["screenrecord", "--verbose", "--time-limit", &time_limit, &remote],
+            );
+
+            // Only count the segment if screenrecord actually ran (a non-zero/errored
+            // result means nothing was captured to `remote`), otherwise a device that's
+            // gone or a missing screenrecord binary would spin this loop pushing bogus
+            // paths as fast as the OS can spawn adb.
+            let succeeded = result.map(|o| o.status.success()).unwrap_or(false);
+            if succeeded {
+                if let Ok(mut segs) = worker_segments.lock() {
+                    segs.push(remote.clone());
+                }
+                consecutive_failures = 0;
+            } else {
+                consecutive_failures += 1;
+                if consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+                    break;
+                }
+                thread::sleep(SEGMENT_RETRY_DELAY);
+            }
+
+            index += 1;
+        }
+    });
+
+    let session = RecordingSession { should_stop, segments, worker: Some(worker) };
+    state.0.lock().map_err(|e| e.to_string())?.insert(device, session);
 
     Ok("Recording started".to_string())
 }
 
 #[tauri::command]
-pub async fn stop_screen_recording(device: String, local_path: String) -> Result<String, String> {
-    // 1. Send SIGINT (2) to screenrecord to make it finalize the MP4
-    // We use 'pkill -2 -l screenrecord' (matches name exactly? no -l is signal list? pkill -2 -f screenrecord?)
-    // 'killall -2 screenrecord' is common.
-    
-    let kill_output = Command::new("adb")
-        .args(&["-s", &device, "shell", "pkill", "-2", "screenrecord"])
-        .output()
-        .map_err(|e| format!("Failed to run pkill: {}", e))?;
-        
-    // If pkill fails (e.g. old android), try killall
-    if !kill_output.status.success() {
-         let _ = Command::new("adb")
-            .args(&["-s", &device, "shell", "killall", "-2", "screenrecord"])
-            .output();
+pub async fn stop_screen_recording(state: State<'_, RecordingState>, device: String, local_path: String) -> Result<String, String> {
+    let mut session = {
+        let mut procs = state.0.lock().map_err(|e| e.to_string())?;
+        procs.remove(&device).ok_or_else(|| format!("No recording running for {}", device))?
+    };
+
+    // Signal the worker to stop, then send the graceful interrupt so the current
+    // screenrecord segment finalizes its MP4 header before we pull it.
+    session.should_stop.store(true, Ordering::Relaxed);
+
+    let kill = adb_shell(&device, &["pkill", "-2", "screenrecord"]);
+    if kill.map(|o| !o.status.success()).unwrap_or(true) {
+        // Older Android ships killall instead of pkill.
+        let _ = adb_shell(&device, &["killall", "-2", "screenrecord"]);
+    }
+
+    // Let the worker observe the stop and exit its relaunch loop.
+    if let Some(handle) = session.worker.take() {
+        let _ = handle.join();
     }
 
-    // 2. Wait a bit for file to finalize
+    // Give the device a moment to flush the final segment to disk.
     thread::sleep(Duration::from_secs(2));
 
-    // 3. Pull the file
-    let pull_output = Command::new("adb")
-        .args(&["-s", &device, "pull", "/sdcard/robot_runner_rec.mp4", &local_path])
-        .output()
-        .map_err(|e| format!("Failed to pull video: {}", e))?;
+    let segments = session.segments.lock().map_err(|e| e.to_string())?.clone();
+    if segments.is_empty() {
+        return Err("No recording segments captured".to_string());
+    }
+
+    // Pull each segment next to the requested output path. Local temp names are
+    // namespaced by the device stem too (like the remote segment paths already are),
+    // so two devices stopped concurrently with outputs in the same directory don't
+    // collide on each other's segment/concat-list files.
+    let stem = safe_stem(&device);
+    let out_path = std::path::PathBuf::from(&local_path);
+    let parent = out_path.parent().map(|p| p.to_path_buf()).unwrap_or_default();
+    let mut local_segments = Vec::new();
+    for (i, remote) in segments.iter().enumerate() {
+        let local_seg = parent.join(format!("robot_runner_seg_{}_{}.mp4", stem, i));
+        let local_seg_str = local_seg.to_string_lossy().to_string();
+        let pull = Command::new("adb")
+            .args(&["-s", &device, "pull", remote, &local_seg_str])
+            .output()
+            .map_err(|e| format!("Failed to pull segment: {}", e))?;
+        if pull.status.success() {
+            local_segments.push(local_seg_str);
+        }
+    }
+
+    if local_segments.is_empty() {
+        return Err("Failed to pull any recording segments".to_string());
+    }
+
+    // Concatenate locally. A single segment is already the final MP4; multiple
+    // segments go through ffmpeg's concat demuxer.
+    if local_segments.len() == 1 {
+        std::fs::rename(&local_segments[0], &local_path)
+            .map_err(|e| format!("Failed to finalize recording: {}", e))?;
+    } else {
+        let list_path = parent.join(format!("robot_runner_concat_{}.txt", stem));
+        let mut list = String::new();
+        for seg in &local_segments {
+            // ffmpeg concat list entries quote the path with single quotes.
+            list.push_str(&format!("file '{}'\n", seg.replace('\'', r"'\''")));
+        }
+        std::fs::write(&list_path, list).map_err(|e| e.to_string())?;
 
-    if !pull_output.status.success() {
-        return Err(format!("Failed to pull video: {}", String::from_utf8_lossy(&pull_output.stderr)));
+        let concat = Command::new("ffmpeg")
+            .args(&["-y", "-f", "concat", "-safe", "0", "-i"])
+            .arg(&list_path)
+            .args(&["-c", "copy"])
+            .arg(&local_path)
+            .output()
+            .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+
+        let _ = std::fs::remove_file(&list_path);
+        if !concat.status.success() {
+            return Err(format!("ffmpeg concat failed: {}", String::from_utf8_lossy(&concat.stderr)));
+        }
+        for seg in &local_segments {
+            let _ = std::fs::remove_file(seg);
+        }
     }
 
-    // 4. Delete temp file
-    let _ = Command::new("adb")
-        .args(&["-s", &device, "shell", "rm", "/sdcard/robot_runner_rec.mp4"])
-        .output();
+    // Clean up the remote segment files.
+    for remote in &segments {
+        let _ = adb_shell(&device, &["rm", remote]);
+    }
 
     Ok(local_path)
 }
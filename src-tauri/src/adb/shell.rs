@@ -1,12 +1,18 @@
-use std::process::{Command, Child, Stdio};
-use std::sync::Mutex;
-use std::collections::HashMap;
-use std::io::{BufRead, BufReader};
-use tauri::{command, AppHandle, Emitter, State};
+use std::process::Command;
+use async_process::{Command as AsyncCommand, Stdio};
+use futures_lite::{io::BufReader, AsyncBufReadExt, StreamExt};
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread;
+use std::collections::HashMap;
+use portable_pty::{native_pty_system, CommandBuilder, MasterPty, PtySize};
+use tauri::{command, AppHandle, Emitter, Manager, State};
 
 pub struct ShellState {
-    pub running_commands: Mutex<HashMap<String, Child>>,
+    // run id -> pid of the live child, so stop_adb_command can signal it. The child
+    // itself lives in the Tokio task awaiting its exit.
+    pub running_commands: Mutex<HashMap<String, u32>>,
 }
 
 impl Default for ShellState {
@@ -17,6 +23,143 @@ impl Default for ShellState {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Interactive shell sessions (PTY-backed)
+// ---------------------------------------------------------------------------
+//
+// `run_adb_command`/`start_adb_command` pipe stdio, which is fine for one-shot
+// commands but breaks anything that checks `isatty` — color output, `top`, `su`,
+// pagers, interactive prompts. An interactive session instead spawns `adb -s
+// <device> shell` attached to a real pseudo-terminal, the way `openpty` gives a
+// master/slave fd pair on Unix: the child is attached to the slave side, and we
+// keep the master side open for reading/writing and resizing. `portable_pty`
+// abstracts that pairing (and ConPTY on Windows) behind one API.
+
+static SESSION_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// One live `adb shell` session. The reader thread feeds `buffer`; `writer` and
+/// `master` (for resize) are kept so the Tauri commands can reach the PTY directly.
+pub struct PtySession {
+    master: Box<dyn MasterPty + Send>,
+    writer: Mutex<Box<dyn Write + Send>>,
+    buffer: Arc<Mutex<Vec<u8>>>,
+    // Bytes dropped off the front of `buffer` by the cap, so offsets stay monotonic.
+    trimmed: Arc<AtomicUsize>,
+}
+
+pub struct ShellPtyState(pub Mutex<HashMap<String, PtySession>>);
+
+impl Default for ShellPtyState {
+    fn default() -> Self {
+        Self(Mutex::new(HashMap::new()))
+    }
+}
+
+const SHELL_BUFFER_CAP: usize = 1_000_000;
+
+/// Start an `adb -s <device> shell` session behind a pty and return its session id.
+/// Output streams into an in-memory buffer the frontend drains via
+/// `fetch_shell_buffer`, mirroring `fetch_logcat_buffer`'s offset cursor.
+#[command]
+pub fn shell_open(state: State<'_, ShellPtyState>, device: String) -> Result<String, String> {
+    let pty_system = native_pty_system();
+    let pair = pty_system
+        .openpty(PtySize { rows: 24, cols: 80, pixel_width: 0, pixel_height: 0 })
+        .map_err(|e| e.to_string())?;
+
+    let mut cmd = CommandBuilder::new("adb");
+    cmd.args(["-s", &device, "shell"]);
+
+    // The child attaches to the slave side; once spawned we only need the master.
+    pair.slave.spawn_command(cmd).map_err(|e| e.to_string())?;
+    drop(pair.slave);
+
+    let mut reader = pair.master.try_clone_reader().map_err(|e| e.to_string())?;
+    let writer = pair.master.take_writer().map_err(|e| e.to_string())?;
+
+    let buffer: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
+    let reader_buffer = buffer.clone();
+    let trimmed = Arc::new(AtomicUsize::new(0));
+    let reader_trimmed = trimmed.clone();
+
+    // The pty's reader is a blocking `Read`, so it gets its own OS thread rather
+    // than sharing the async runtime used for logcat/robot output.
+    thread::spawn(move || {
+        let mut chunk = [0u8; 4096];
+        loop {
+            match reader.read(&mut chunk) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    if let Ok(mut b) = reader_buffer.lock() {
+                        b.extend_from_slice(&chunk[..n]);
+                        if b.len() > SHELL_BUFFER_CAP {
+                            let excess = b.len() - SHELL_BUFFER_CAP;
+                            b.drain(0..excess);
+                            reader_trimmed.fetch_add(excess, Ordering::SeqCst);
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    let session_id = format!("{}-{}", device, SESSION_COUNTER.fetch_add(1, Ordering::SeqCst));
+    let session = PtySession { master: pair.master, writer: Mutex::new(writer), buffer, trimmed };
+    state.0.lock().map_err(|e| e.to_string())?.insert(session_id.clone(), session);
+
+    Ok(session_id)
+}
+
+/// Write raw input (keystrokes, pasted text) to the session's pty master.
+#[command]
+pub fn shell_write(state: State<'_, ShellPtyState>, session_id: String, data: String) -> Result<(), String> {
+    let procs = state.0.lock().map_err(|e| e.to_string())?;
+    let session = procs.get(&session_id).ok_or_else(|| format!("Session {} not found", session_id))?;
+    let mut writer = session.writer.lock().map_err(|e| e.to_string())?;
+    writer.write_all(data.as_bytes()).map_err(|e| e.to_string())?;
+    writer.flush().map_err(|e| e.to_string())
+}
+
+/// Tell the remote pty to match the frontend terminal's size, so full-screen tools
+/// (top, less, vim) lay out correctly.
+#[command]
+pub fn shell_resize(state: State<'_, ShellPtyState>, session_id: String, rows: u16, cols: u16) -> Result<(), String> {
+    let procs = state.0.lock().map_err(|e| e.to_string())?;
+    let session = procs.get(&session_id).ok_or_else(|| format!("Session {} not found", session_id))?;
+    session
+        .master
+        .resize(PtySize { rows, cols, pixel_width: 0, pixel_height: 0 })
+        .map_err(|e| e.to_string())
+}
+
+/// Drain new output since `offset`, returning it alongside the total number of bytes
+/// received so far so the caller can pass that back as the next offset — the same
+/// cursor contract as `fetch_logcat_buffer`.
+#[command]
+pub fn fetch_shell_buffer(
+    state: State<'_, ShellPtyState>,
+    session_id: String,
+    offset: usize,
+) -> Result<(String, usize), String> {
+    let procs = state.0.lock().map_err(|e| e.to_string())?;
+
+    if let Some(session) = procs.get(&session_id) {
+        let buf = session.buffer.lock().map_err(|e| e.to_string())?;
+        let trimmed = session.trimmed.load(Ordering::SeqCst);
+        let local = offset.saturating_sub(trimmed).min(buf.len());
+        Ok((String::from_utf8_lossy(&buf[local..]).to_string(), trimmed + buf.len()))
+    } else {
+        Ok((String::new(), 0))
+    }
+}
+
+/// End a session: dropping its `PtySession` closes the master fd/writer, which ends
+/// the attached `adb shell` child and its reader thread.
+#[command]
+pub fn shell_close(state: State<'_, ShellPtyState>, session_id: String) -> Result<(), String> {
+    state.0.lock().map_err(|e| e.to_string())?.remove(&session_id);
+    Ok(())
+}
 
 
 #[command]
@@ -63,11 +206,11 @@ pub fn start_adb_command(
     command: String
 ) -> Result<(), String> {
     // Split command string into args
-    let args: Vec<&str> = command.split_whitespace().collect();
-    
-    let mut cmd = Command::new("adb");
+    let args: Vec<String> = command.split_whitespace().map(|s| s.to_string()).collect();
+
+    let mut cmd = AsyncCommand::new("adb");
     cmd.arg("-s").arg(&device).args(&args);
-    
+
     // We need pipes for output
     cmd.stdout(Stdio::piped());
     cmd.stderr(Stdio::piped());
@@ -75,37 +218,55 @@ pub fn start_adb_command(
     #[cfg(target_os = "windows")]
     {
         use std::os::windows::process::CommandExt;
-        cmd.creation_flags(0x08000000); 
+        cmd.creation_flags(0x08000000);
     }
 
     let mut child = cmd.spawn().map_err(|e| e.to_string())?;
-    
     let stdout = child.stdout.take().ok_or("Failed to open stdout")?;
-    // let stderr = child.stderr.take().ok_or("Failed to open stderr")?;
-    
-    let id_clone = id.clone();
+
+    state.running_commands.lock().unwrap().insert(id.clone(), child.id());
+
+    // Stream stdout over cmd-output-{id} through an async line reader, then await the
+    // real exit future to emit cmd-close-{id} the instant the process exits.
     let app_clone = app.clone();
-    
-    // stdout thread
-    thread::spawn(move || {
-        let reader = BufReader::new(stdout);
-        for line in reader.lines() {
-            if let Ok(l) = line {
-                 let _ = app_clone.emit(&format!("cmd-output-{}", id_clone), l);
-            }
+    tauri::async_runtime::spawn(async move {
+        let out_event = format!("cmd-output-{}", id);
+        let mut lines = BufReader::new(stdout).lines();
+        while let Some(Ok(l)) = lines.next().await {
+            let _ = app_clone.emit(&out_event, l);
+        }
+
+        let _ = child.status().await;
+
+        // Drop the handle and notify the frontend.
+        let state = app_clone.state::<ShellState>();
+        if let Ok(mut commands) = state.running_commands.lock() {
+            commands.remove(&id);
         }
-        let _ = app_clone.emit(&format!("cmd-close-{}", id_clone), "Process finished");
+        let _ = app_clone.emit(&format!("cmd-close-{}", id), "Process finished");
     });
 
-    state.running_commands.lock().unwrap().insert(id, child);
     Ok(())
 }
 
 #[command]
 pub fn stop_adb_command(state: State<'_, ShellState>, id: String) -> Result<(), String> {
     let mut commands = state.running_commands.lock().unwrap();
-    if let Some(mut child) = commands.remove(&id) {
-        child.kill().map_err(|e| e.to_string())?;
+    if let Some(pid) = commands.remove(&id) {
+        #[cfg(target_os = "windows")]
+        {
+            use std::os::windows::process::CommandExt;
+            let _ = Command::new("taskkill")
+                .args(&["/F", "/T", "/PID", &pid.to_string()])
+                .creation_flags(0x08000000)
+                .output();
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            use nix::sys::signal::{self, Signal};
+            use nix::unistd::Pid;
+            let _ = signal::kill(Pid::from_raw(pid as i32), Signal::SIGKILL);
+        }
         Ok(())
     } else {
         Err("Command not found".to_string())
@@ -113,15 +274,16 @@ pub fn stop_adb_command(state: State<'_, ShellState>, id: String) -> Result<(),
 }
 
 #[command]
-pub fn restart_adb_server() -> Result<String, String> {
-    use std::os::windows::process::CommandExt;
-    
+pub fn restart_adb_server(app: AppHandle) -> Result<String, String> {
     // Kill
     let mut kill_cmd = Command::new("adb");
     kill_cmd.arg("kill-server");
     #[cfg(target_os = "windows")]
-    kill_cmd.creation_flags(0x08000000);
-    
+    {
+        use std::os::windows::process::CommandExt;
+        kill_cmd.creation_flags(0x08000000);
+    }
+
     let kill_output = kill_cmd.output()
         .map_err(|e| format!("Failed to kill server: {}", e))?;
 
@@ -129,12 +291,21 @@ pub fn restart_adb_server() -> Result<String, String> {
     let mut start_cmd = Command::new("adb");
     start_cmd.arg("start-server");
     #[cfg(target_os = "windows")]
-    start_cmd.creation_flags(0x08000000);
+    {
+        use std::os::windows::process::CommandExt;
+        start_cmd.creation_flags(0x08000000);
+    }
 
     let start_output = start_cmd.output()
         .map_err(|e| format!("Failed to start server: {}", e))?;
 
-    Ok(format!("Server Restarted.\nKill: {}\nStart: {}", 
+    // Bouncing the server drops the old track-devices stream; the device monitor
+    // reconnects on its own, and start_device_monitor is idempotent, so this just
+    // makes sure one is running.
+    let monitor_state = app.state::<crate::adb::tracker::DeviceMonitorState>();
+    let _ = crate::adb::tracker::start_device_monitor(app.clone(), monitor_state);
+
+    Ok(format!("Server Restarted.\nKill: {}\nStart: {}",
         String::from_utf8_lossy(&kill_output.stdout),
         String::from_utf8_lossy(&start_output.stdout)))
 }
@@ -1,61 +1,480 @@
 use std::process::Command;
+use std::time::Duration;
+
+use async_io::Timer;
 use serde::Serialize;
 
+// How far apart the two /proc/stat + /proc/net/dev samples are taken; long enough for
+// the counters to move meaningfully, short enough not to make get_device_stats feel slow.
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+
+// Default display refresh rate used for the jank budget when the caller doesn't supply
+// one; most devices this tool targets run 60Hz.
+const DEFAULT_REFRESH_HZ: f64 = 60.0;
+
 #[derive(Debug, Serialize, Default)]
 pub struct AppStats {
     pub cpu_usage: f32, // Percentage
-    pub ram_used: u64,  // KB
-    pub fps: u32,       // Frames per second
+    pub ram_used: MemoryBreakdown,
+    pub frame_stats: FrameStats,
+    pub disk_io: DiskIoStats,
+}
+
+// Derived from `dumpsys gfxinfo <pkg> framestats`'s per-frame CSV rows, instead of just
+// an averaged FPS that hides stutter. `jank_percent` is the share of frames whose render
+// time (FrameCompleted - IntendedVsync) exceeded the frame budget for `refresh_hz`
+// (16.67ms at the default 60Hz); `p50/p90/p99_ms` are render-time percentiles over the
+// same window.
+#[derive(Debug, Serialize, Default, Clone, Copy)]
+pub struct FrameStats {
+    pub fps: u32,
+    pub jank_percent: f32,
+    pub p50_ms: f32,
+    pub p90_ms: f32,
+    pub p99_ms: f32,
+    pub frame_count: u32,
+}
+
+// Parsed from `dumpsys meminfo <pkg>`'s "App Summary" section (each line is
+// `<Label>: <PSS-in-KB>`), so a leak can be traced to native heap vs. graphics vs. Java
+// heap instead of just watching one opaque total move. `total_pss` is always populated,
+// falling back to the legacy single `TOTAL`/`Total PSS:` line when the App Summary
+// section itself is missing (older Android), leaving the rest of the breakdown at 0.
+#[derive(Debug, Serialize, Default, Clone, Copy)]
+pub struct MemoryBreakdown {
+    pub java_heap: u64,
+    pub native_heap: u64,
+    pub code: u64,
+    pub stack: u64,
+    pub graphics: u64,
+    pub private_other: u64,
+    pub system: u64,
+    pub total_pss: u64,
 }
 
 #[derive(Debug, Serialize, Default)]
 pub struct DeviceStats {
-    pub cpu_usage: f32, 
-    pub ram_used: u64,  
-    pub ram_total: u64, 
-    pub battery_level: u8,
+    pub cpu_usage: f32,
+    pub cpu_per_core: Vec<f32>,
+    pub ram_used: u64,
+    pub ram_total: u64,
+    pub battery: BatteryInfo,
+    pub network: NetworkStats,
+    pub disk_io: DiskIoStats,
     pub app_stats: Option<AppStats>,
+    pub thermal_zones: Vec<ThermalZone>,
+}
+
+// Parsed from the same `dumpsys battery` dump `level` already came from. `voltage` is
+// millivolts and `temperature_celsius` is deci-degrees/10, matching the units `dumpsys`
+// itself reports; `status`/`health` are left as the raw BatteryManager integer codes
+// (e.g. status 2 = charging, health 2 = good) rather than mapped to strings, since
+// that's the one stable contract across Android versions.
+#[derive(Debug, Serialize, Default, Clone, Copy)]
+pub struct BatteryInfo {
+    pub level: u8,
+    pub temperature_celsius: f32,
+    pub voltage_mv: i32,
+    pub status: u8,
+    pub health: u8,
+}
+
+// One `/sys/class/thermal/thermal_zone*` reading. `label` is that zone's `type` file
+// (e.g. "battery", "cpu0", "gpu"); names and zone counts vary by SoC vendor, so this is
+// reported as a flat list rather than fixed fields.
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct ThermalZone {
+    pub label: String,
+    pub celsius: f32,
+}
+
+#[derive(Debug, Serialize, Default, Clone, Copy)]
+pub struct NetworkStats {
+    pub rx_bytes_per_sec: f64,
+    pub tx_bytes_per_sec: f64,
+    pub rx_total: u64,
+    pub tx_total: u64,
+    // wlan0 tx rate in Mbit/s: the negotiated PHY rate from `iw`, or, when that's
+    // unavailable, a tx throughput estimate from wlan0's sysfs byte counter. `None`
+    // when neither source produces a value.
+    pub wlan0_link_mbps: Option<f32>,
+}
+
+// Mirrors `NetworkStats`'s rx/tx shape for reads/writes, same delta-over-interval
+// contract: `*_total` is the latest cumulative counter, `*_bytes_per_sec` is the delta
+// across the sampling interval divided by its length.
+#[derive(Debug, Serialize, Default, Clone, Copy)]
+pub struct DiskIoStats {
+    pub read_bytes_per_sec: f64,
+    pub write_bytes_per_sec: f64,
+    pub read_total: u64,
+    pub write_total: u64,
 }
 
 #[tauri::command]
-pub async fn get_device_stats(device: String, package: Option<String>) -> Result<DeviceStats, String> {
-    // 1. Get Battery Level
+pub async fn get_device_stats(
+    device: String,
+    package: Option<String>,
+    refresh_hz: Option<f64>,
+) -> Result<DeviceStats, String> {
+    // 1. Get Battery Info
     let bat_output = run_adb_shell(&device, "dumpsys battery");
-    let battery_level = parse_battery_level(&bat_output).unwrap_or(0);
+    let battery = parse_battery_info(&bat_output).unwrap_or_default();
+
+    // 1b. Get Thermal Zones. Throttling is a common cause of performance regressions in
+    // long test sessions, so surfacing zone temperatures alongside CPU/FPS lets users
+    // correlate throttling with the drops those metrics show.
+    let thermal_zones = get_thermal_zones(&device);
 
     // 2. Get System RAM Info
     let mem_output = run_adb_shell(&device, "cat /proc/meminfo");
     let (ram_total, ram_used) = parse_mem_info(&mem_output).unwrap_or((0, 0));
 
-    // 3. Get System CPU Info (Simplified top)
-    let top_output = run_adb_shell(&device, "top -n 1 -m 5"); 
-    let cpu_usage = parse_cpu_usage(&top_output).unwrap_or(0.0);
+    // 3. Get System CPU + network + disk throughput via a shared pair of delta samples.
+    // A single `top -n1` snapshot reads %idle instantaneously and double-counts across
+    // cores; diffing two /proc/stat + /proc/net/dev + /proc/diskstats reads a short
+    // interval apart is what a proper monitor does, and reusing the same two round
+    // trips for all three avoids tripling the command's latency.
+    let (cpu_usage, cpu_per_core, mut network, disk_io) = sample_system_usage(&device, SAMPLE_INTERVAL).await;
+    network.wlan0_link_mbps = get_wlan0_link_mbps(&device).await;
 
-    // 4. Get App Stats (if package provided)
+    // 4. Get App Stats (if package provided). Per-app %CPU still comes from `top`,
+    // since /proc/stat doesn't break usage out by package.
     let mut app_stats = None;
     if let Some(pkg) = package {
         if !pkg.is_empty() {
+             let top_output = run_adb_shell(&device, "top -n 1 -m 5");
              let app_cpu = parse_app_cpu(&top_output, &pkg).unwrap_or(0.0);
-             let app_ram = get_app_ram(&device, &pkg).unwrap_or(0);
-             let app_fps = get_app_fps(&device, &pkg).unwrap_or(0);
+             let app_ram = get_app_ram(&device, &pkg).unwrap_or_default();
+             let frame_stats = get_app_frame_stats(&device, &pkg, refresh_hz.unwrap_or(DEFAULT_REFRESH_HZ)).unwrap_or_default();
+             let app_disk_io = get_app_disk_io(&device, &pkg, SAMPLE_INTERVAL).await.unwrap_or_default();
 
              app_stats = Some(AppStats {
                  cpu_usage: app_cpu,
                  ram_used: app_ram,
-                 fps: app_fps,
+                 frame_stats,
+                 disk_io: app_disk_io,
              });
         }
     }
 
     Ok(DeviceStats {
         cpu_usage,
+        cpu_per_core,
         ram_used,
         ram_total,
-        battery_level,
+        battery,
+        network,
+        disk_io,
         app_stats,
+        thermal_zones,
+    })
+}
+
+// One cpu/cpuN line's accumulated tick counters, reduced to just what the usage
+// formula needs.
+struct CpuTimes {
+    total: u64,
+    idle_all: u64,
+}
+
+// Parses `/proc/stat`'s `cpu` (aggregate) and `cpuN` (per-core) lines. Fields are
+// `user nice system idle iowait irq softirq steal [guest] [guest_nice]`; `idle_all` is
+// `idle + iowait` and `total` is the sum of every field present, so this still works on
+// kernels that don't report the trailing guest columns.
+fn parse_proc_stat(output: &str) -> (Option<CpuTimes>, Vec<CpuTimes>) {
+    let mut aggregate = None;
+    let mut cores = Vec::new();
+
+    for line in output.lines() {
+        let mut parts = line.split_whitespace();
+        let Some(label) = parts.next() else { continue };
+        if !label.starts_with("cpu") {
+            continue;
+        }
+
+        let fields: Vec<u64> = parts.filter_map(|p| p.parse::<u64>().ok()).collect();
+        if fields.len() < 4 {
+            continue;
+        }
+
+        let times = CpuTimes {
+            total: fields.iter().sum(),
+            idle_all: fields[3] + fields.get(4).copied().unwrap_or(0),
+        };
+
+        if label == "cpu" {
+            aggregate = Some(times);
+        } else {
+            cores.push(times);
+        }
+    }
+
+    (aggregate, cores)
+}
+
+// usage% = (total_delta - idle_all_delta) / total_delta * 100. If no ticks elapsed
+// between the two samples (total_delta == 0), dividing would give a meaningless
+// NaN/inf, so fall back to `previous_usage` instead.
+fn cpu_usage_from_delta(prev: &CpuTimes, curr: &CpuTimes, previous_usage: f32) -> f32 {
+    let total_delta = curr.total.saturating_sub(prev.total);
+    if total_delta == 0 {
+        return previous_usage;
+    }
+
+    let idle_delta = curr.idle_all.saturating_sub(prev.idle_all);
+    (total_delta.saturating_sub(idle_delta) as f32 / total_delta as f32) * 100.0
+}
+
+// Marks where /proc/stat's output ends and /proc/net/dev's begins in the combined
+// snapshot command below, so one shell round trip can feed both parsers.
+const NET_STATS_MARKER: &str = "NET_STATS_MARKER";
+
+// `/proc/net/dev` lines look like `iface: rx_bytes rx_packets ... tx_bytes tx_packets
+// ...` (rx is fields 0-7 after the colon, tx starts at field 8). Sums every interface
+// except the loopback; the two header lines have no colon and are skipped naturally.
+fn parse_proc_net_dev(output: &str) -> (u64, u64) {
+    let mut rx_total = 0u64;
+    let mut tx_total = 0u64;
+
+    for line in output.lines() {
+        let Some((iface, rest)) = line.split_once(':') else { continue };
+        let iface = iface.trim();
+        if iface.is_empty() || iface == "lo" {
+            continue;
+        }
+
+        let fields: Vec<&str> = rest.split_whitespace().collect();
+        if fields.len() < 9 {
+            continue;
+        }
+
+        rx_total += fields[0].parse::<u64>().unwrap_or(0);
+        tx_total += fields[8].parse::<u64>().unwrap_or(0);
+    }
+
+    (rx_total, tx_total)
+}
+
+// Marks where /proc/net/dev's output ends and /proc/diskstats's begins in the combined
+// snapshot below, so the same two round trips that already cover CPU + network can
+// cover disk too.
+const DISK_STATS_MARKER: &str = "DISK_STATS_MARKER";
+
+// `/proc/diskstats` fields are 1-indexed per the kernel docs: field 3 is the device
+// name, field 6 is sectors read, field 10 is sectors written. Sums every block device
+// except loopback/ramdisk (neither reflects real storage I/O), converting sectors to
+// bytes with the standard 512-byte sector size.
+fn parse_proc_diskstats(output: &str) -> (u64, u64) {
+    let mut sectors_read = 0u64;
+    let mut sectors_written = 0u64;
+
+    for line in output.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 14 {
+            continue;
+        }
+
+        let name = fields[2];
+        if name.starts_with("loop") || name.starts_with("ram") {
+            continue;
+        }
+
+        sectors_read += fields[5].parse::<u64>().unwrap_or(0);
+        sectors_written += fields[9].parse::<u64>().unwrap_or(0);
+    }
+
+    (sectors_read * 512, sectors_written * 512)
+}
+
+fn parse_combined_snapshot(output: &str) -> (Option<CpuTimes>, Vec<CpuTimes>, (u64, u64), (u64, u64)) {
+    let (stat_part, rest) = output.split_once(NET_STATS_MARKER).unwrap_or((output, ""));
+    let (net_part, disk_part) = rest.split_once(DISK_STATS_MARKER).unwrap_or((rest, ""));
+    let (aggregate, cores) = parse_proc_stat(stat_part);
+    let net = parse_proc_net_dev(net_part);
+    let disk = parse_proc_diskstats(disk_part);
+    (aggregate, cores, net, disk)
+}
+
+// Takes two combined /proc/stat + /proc/net/dev + /proc/diskstats snapshots `interval`
+// apart (one shell round trip each) and returns (aggregate CPU usage%, per-core usage%,
+// NetworkStats, DiskIoStats).
+async fn sample_system_usage(device: &str, interval: Duration) -> (f32, Vec<f32>, NetworkStats, DiskIoStats) {
+    let cmd = format!(
+        "cat /proc/stat; echo {}; cat /proc/net/dev; echo {}; cat /proc/diskstats",
+        NET_STATS_MARKER, DISK_STATS_MARKER
+    );
+
+    let first = run_adb_shell(device, &cmd);
+    let (first_agg, first_cores, (first_rx, first_tx), (first_read, first_write)) = parse_combined_snapshot(&first);
+
+    Timer::after(interval).await;
+
+    let second = run_adb_shell(device, &cmd);
+    let (second_agg, second_cores, (second_rx, second_tx), (second_read, second_write)) = parse_combined_snapshot(&second);
+
+    let cpu_usage = match (first_agg, second_agg) {
+        (Some(prev), Some(curr)) => cpu_usage_from_delta(&prev, &curr, 0.0),
+        _ => 0.0,
+    };
+
+    let cpu_per_core = first_cores
+        .iter()
+        .zip(second_cores.iter())
+        .map(|(prev, curr)| cpu_usage_from_delta(prev, curr, 0.0))
+        .collect();
+
+    let interval_secs = interval.as_secs_f64();
+    let network = NetworkStats {
+        rx_bytes_per_sec: if interval_secs > 0.0 {
+            second_rx.saturating_sub(first_rx) as f64 / interval_secs
+        } else {
+            0.0
+        },
+        tx_bytes_per_sec: if interval_secs > 0.0 {
+            second_tx.saturating_sub(first_tx) as f64 / interval_secs
+        } else {
+            0.0
+        },
+        rx_total: second_rx,
+        tx_total: second_tx,
+        wlan0_link_mbps: None, // filled in by the caller via get_wlan0_link_mbps
+    };
+
+    let disk_io = DiskIoStats {
+        read_bytes_per_sec: if interval_secs > 0.0 {
+            second_read.saturating_sub(first_read) as f64 / interval_secs
+        } else {
+            0.0
+        },
+        write_bytes_per_sec: if interval_secs > 0.0 {
+            second_write.saturating_sub(first_write) as f64 / interval_secs
+        } else {
+            0.0
+        },
+        read_total: second_read,
+        write_total: second_write,
+    };
+
+    (cpu_usage, cpu_per_core, network, disk_io)
+}
+
+// Best-effort current `wlan0` PHY tx rate in Mbit/s, parsed from `iw dev wlan0 link`'s
+// "tx bitrate: 866.7 MBit/s ..." line. Devices without `iw`, or with wlan0
+// disassociated, won't have that line; fall back to a rough throughput estimate from
+// /sys/class/net/wlan0/statistics/tx_bytes sampled across SAMPLE_INTERVAL, since that's
+// the only other per-interface counter exposed without shelling out to `iw`.
+async fn get_wlan0_link_mbps(device: &str) -> Option<f32> {
+    let output = run_adb_shell(device, "iw dev wlan0 link");
+    for line in output.lines() {
+        if let Some(rest) = line.trim().strip_prefix("tx bitrate:") {
+            if let Some(value) = rest.split_whitespace().next() {
+                if let Ok(mbps) = value.parse::<f32>() {
+                    return Some(mbps);
+                }
+            }
+        }
+    }
+
+    let path = "/sys/class/net/wlan0/statistics/tx_bytes";
+    let first = run_adb_shell(device, &format!("cat {}", path)).trim().parse::<u64>().ok()?;
+    Timer::after(SAMPLE_INTERVAL).await;
+    let second = run_adb_shell(device, &format!("cat {}", path)).trim().parse::<u64>().ok()?;
+
+    let bytes_per_sec = second.saturating_sub(first) as f64 / SAMPLE_INTERVAL.as_secs_f64();
+    Some((bytes_per_sec * 8.0 / 1_000_000.0) as f32)
+}
+
+// Parses `read_bytes: 1234` / `write_bytes: 1234` out of a `/proc/<pid>/io` dump.
+fn parse_proc_io(output: &str, label: &str) -> Option<u64> {
+    output
+        .lines()
+        .find(|line| line.trim().starts_with(label))
+        .and_then(|line| line.split_once(':'))
+        .and_then(|(_, val)| val.trim().parse::<u64>().ok())
+}
+
+// Per-package disk I/O rate, resolved via `pidof` then two `/proc/<pid>/io` reads
+// `interval` apart (mirrors the wlan0 sysfs-counter fallback above: a plain delta over
+// a fixed window since /proc/<pid>/io only exposes cumulative counters). `None` if the
+// package has no running process to resolve a PID for.
+async fn get_app_disk_io(device: &str, package: &str, interval: Duration) -> Option<DiskIoStats> {
+    let pid = run_adb_shell(device, &format!("pidof {}", package))
+        .split_whitespace()
+        .next()?
+        .to_string();
+
+    let path = format!("/proc/{}/io", pid);
+    let first = run_adb_shell(device, &format!("cat {}", path));
+    let (first_read, first_write) = (
+        parse_proc_io(&first, "read_bytes")?,
+        parse_proc_io(&first, "write_bytes")?,
+    );
+
+    Timer::after(interval).await;
+
+    let second = run_adb_shell(device, &format!("cat {}", path));
+    let (second_read, second_write) = (
+        parse_proc_io(&second, "read_bytes")?,
+        parse_proc_io(&second, "write_bytes")?,
+    );
+
+    let interval_secs = interval.as_secs_f64();
+    Some(DiskIoStats {
+        read_bytes_per_sec: if interval_secs > 0.0 {
+            second_read.saturating_sub(first_read) as f64 / interval_secs
+        } else {
+            0.0
+        },
+        write_bytes_per_sec: if interval_secs > 0.0 {
+            second_write.saturating_sub(first_write) as f64 / interval_secs
+        } else {
+            0.0
+        },
+        read_total: second_read,
+        write_total: second_write,
     })
 }
 
+// Emitted after *both* a zone's `type` read and its `temp` read, so each zone
+// contributes exactly two marker-delimited segments. A missing/unreadable file just
+// yields an empty segment in its own slot instead of shifting every later zone's
+// fields, the way alternating on every output line used to.
+const THERMAL_ZONE_MARKER: &str = "THERMAL_ZONE_MARKER";
+
+// Reads every `/sys/class/thermal/thermal_zone*/type` and `.../temp` in one shell round
+// trip. Zone numbering, count, and which `type` strings exist are SoC-vendor-specific,
+// so zones with unreadable type/temp files (missing zone, permission denied) are just
+// skipped rather than padded with placeholders.
+fn get_thermal_zones(device: &str) -> Vec<ThermalZone> {
+    let cmd = format!(
+        "for z in /sys/class/thermal/thermal_zone*; do cat $z/type; echo {m}; cat $z/temp; echo {m}; done",
+        m = THERMAL_ZONE_MARKER
+    );
+    let output = run_adb_shell(device, &cmd);
+
+    let segments: Vec<&str> = output.split(THERMAL_ZONE_MARKER).collect();
+
+    let mut zones = Vec::new();
+    for pair in segments.chunks_exact(2) {
+        let label = pair[0].trim();
+        let temp = pair[1].trim();
+        if label.is_empty() {
+            continue;
+        }
+
+        // temp is in milli-degrees Celsius.
+        if let Ok(milli_celsius) = temp.parse::<f32>() {
+            zones.push(ThermalZone {
+                label: label.to_string(),
+                celsius: milli_celsius / 1000.0,
+            });
+        }
+    }
+
+    zones
+}
+
 fn run_adb_shell(device: &str, command: &str) -> String {
     #[cfg(target_os = "windows")]
     let program = "adb";
@@ -81,7 +500,17 @@ fn run_adb_shell(device: &str, command: &str) -> String {
     }
 }
 
-fn parse_battery_level(output: &str) -> Option<u8> {
+// Finds the first `dumpsys battery` line starting with `label:` and parses the value
+// after the colon, e.g. `parse_battery_field::<u8>(output, "level")` for `  level: 100`.
+fn parse_battery_field<T: std::str::FromStr>(output: &str, label: &str) -> Option<T> {
+    output
+        .lines()
+        .find(|line| line.trim().starts_with(&format!("{}:", label)))
+        .and_then(|line| line.split_once(':'))
+        .and_then(|(_, val)| val.trim().parse::<T>().ok())
+}
+
+fn parse_battery_info(output: &str) -> Option<BatteryInfo> {
     // output example:
     // AC powered: false
     // USB powered: true
@@ -94,13 +523,22 @@ fn parse_battery_level(output: &str) -> Option<u8> {
     // present: true
     // level: 100
     // scale: 100
-    
-    output.lines()
-        .find(|line| line.trim().starts_with("level:"))
-        .and_then(|line| {
-            let parts: Vec<&str> = line.split(':').collect();
-            parts.get(1).and_then(|val| val.trim().parse::<u8>().ok())
-        })
+    // voltage: 4200
+    // temperature: 250
+    // technology: Li-ion
+
+    let level = parse_battery_field::<u8>(output, "level")?;
+
+    Some(BatteryInfo {
+        level,
+        // dumpsys reports temperature in deci-degrees Celsius.
+        temperature_celsius: parse_battery_field::<i32>(output, "temperature")
+            .map(|t| t as f32 / 10.0)
+            .unwrap_or(0.0),
+        voltage_mv: parse_battery_field(output, "voltage").unwrap_or(0),
+        status: parse_battery_field(output, "status").unwrap_or(0),
+        health: parse_battery_field(output, "health").unwrap_or(0),
+    })
 }
 
 fn parse_mem_info(output: &str) -> Option<(u64, u64)> {
@@ -138,36 +576,6 @@ fn extract_kb(line: &str) -> Option<u64> {
         .and_then(|s| s.parse::<u64>().ok())
 }
 
-fn parse_cpu_usage(output: &str) -> Option<f32> {
-    // Format example: "800%cpu  17%user   0%nice 128%sys 648%idle   0%iow   7%irq   0%sirq   0%host"
-    for line in output.lines() {
-        if line.contains("%cpu") {
-             let parts: Vec<&str> = line.split_whitespace().collect();
-             let mut total_cap = 0.0;
-             let mut idle = 0.0;
-             
-             for part in parts {
-                 if part.contains("%cpu") {
-                     if let Ok(val) = part.replace("%cpu", "").parse::<f32>() {
-                         total_cap = val;
-                     }
-                 } else if part.contains("%idle") {
-                     if let Ok(val) = part.replace("%idle", "").parse::<f32>() {
-                         idle = val;
-                     }
-                 }
-             }
-             
-             if total_cap > 0.0 {
-                 let used = total_cap - idle;
-                 let normalized = (used / total_cap) * 100.0;
-                 return Some(normalized);
-             }
-        }
-    }
-    None
-}
-
 fn parse_app_cpu(top_output: &str, package: &str) -> Option<f32> {
     // Header: PID USER PR NI VIRT RES SHR S[%CPU] %MEM TIME+ ARGS
     // We need to find the index of [%CPU] or %CPU
@@ -201,38 +609,87 @@ fn parse_app_cpu(top_output: &str, package: &str) -> Option<f32> {
     None
 }
 
-fn get_app_ram(device: &str, package: &str) -> Option<u64> {
-    // dumpsys meminfo <package>
-    // Look for "TOTAL" row or "Total PSS"
-    let output = run_adb_shell(device, &format!("dumpsys meminfo {}", package));
-    
-    // Output format varies but usually has a "TOTAL" line at bottom of "App Summary" or "Total PSS"
-    // "TOTAL    123456    ..."
-    
-    for line in output.lines() {
+// Finds `label` within `section` and parses the first whitespace-separated token after
+// its colon as a KB value. Handles both `Label:      1234` (own line) and
+// `TOTAL PSS:     1234            TOTAL RSS:    5678 ...` (several labeled columns on
+// one line), since only the first token after the matched label's colon is taken.
+fn extract_label_kb(section: &str, label: &str) -> Option<u64> {
+    for line in section.lines() {
         let trimmed = line.trim();
-        if trimmed.starts_with("TOTAL") || trimmed.starts_with("Total PSS:") {
-             // Extract first number
-             if let Some(val_str) = trimmed.split_whitespace().nth(1) {
-                 if let Ok(val) = val_str.parse::<u64>() {
-                     return Some(val); // In KB usually
-                 }
-             }
+        if let Some(rest) = trimmed.strip_prefix(label) {
+            if let Some(rest) = rest.trim_start().strip_prefix(':') {
+                if let Some(val) = rest.trim_start().split_whitespace().next() {
+                    if let Ok(val) = val.parse::<u64>() {
+                        return Some(val);
+                    }
+                }
+            }
         }
     }
     None
 }
 
-fn get_app_fps(device: &str, package: &str) -> Option<u32> {
+fn get_app_ram(device: &str, package: &str) -> Option<MemoryBreakdown> {
+    let output = run_adb_shell(device, &format!("dumpsys meminfo {}", package));
+
+    let mut breakdown = MemoryBreakdown::default();
+    let mut found_any = false;
+
+    if let Some(idx) = output.find("App Summary") {
+        let section = &output[idx..];
+        if let Some(v) = extract_label_kb(section, "Java Heap") { breakdown.java_heap = v; found_any = true; }
+        if let Some(v) = extract_label_kb(section, "Native Heap") { breakdown.native_heap = v; found_any = true; }
+        if let Some(v) = extract_label_kb(section, "Code") { breakdown.code = v; found_any = true; }
+        if let Some(v) = extract_label_kb(section, "Stack") { breakdown.stack = v; found_any = true; }
+        if let Some(v) = extract_label_kb(section, "Graphics") { breakdown.graphics = v; found_any = true; }
+        if let Some(v) = extract_label_kb(section, "Private Other") { breakdown.private_other = v; found_any = true; }
+        if let Some(v) = extract_label_kb(section, "System") { breakdown.system = v; found_any = true; }
+        if let Some(v) = extract_label_kb(section, "TOTAL PSS") { breakdown.total_pss = v; found_any = true; }
+    }
+
+    // Older Android (or a dump without the App Summary block) only has the legacy
+    // single-line TOTAL/Total PSS fallback that existed before this breakdown did.
+    if breakdown.total_pss == 0 {
+        for line in output.lines() {
+            let trimmed = line.trim();
+            if trimmed.starts_with("TOTAL") || trimmed.starts_with("Total PSS:") {
+                // First numeric token: `TOTAL    1234 ...` and `Total PSS: 1234` alike.
+                if let Some(val) = trimmed.split_whitespace().find_map(|t| t.parse::<u64>().ok()) {
+                    breakdown.total_pss = val;
+                    found_any = true;
+                    break;
+                }
+            }
+        }
+    }
+
+    if found_any {
+        Some(breakdown)
+    } else {
+        None
+    }
+}
+
+// Render-time percentile by index, per the nearest-rank method: `sorted[ceil(p/100 * n) - 1]`.
+// `sorted_render_times_ms` must already be sorted ascending and non-empty.
+fn percentile_ms(sorted_render_times_ms: &[f32], p: f64) -> f32 {
+    let n = sorted_render_times_ms.len();
+    let rank = ((p / 100.0) * n as f64).ceil() as usize;
+    let idx = rank.saturating_sub(1).min(n - 1);
+    sorted_render_times_ms[idx]
+}
+
+fn get_app_frame_stats(device: &str, package: &str, refresh_hz: f64) -> Option<FrameStats> {
     // Use chained command to get stats and uptime together
     // "dumpsys gfxinfo <pkg> framestats" gives CSV data with frame timings.
     // "cat /proc/uptime" gives system uptime in seconds, which matches CLOCK_MONOTONIC used in gfxinfo.
     let cmd = format!("dumpsys gfxinfo {} framestats; echo UPTIME_MARKER; cat /proc/uptime", package);
     let output = run_adb_shell(device, &cmd);
-    
+
     let mut intended_vsyncs: Vec<u64> = Vec::new();
+    let mut render_times_ms: Vec<f32> = Vec::new();
     let mut uptime_ns: u64 = 0;
-    
+
     let mut parsing_uptime = false;
 
     for line in output.lines() {
@@ -260,46 +717,72 @@ fn get_app_fps(device: &str, package: &str) -> Option<u32> {
              // Index 1: IntendedVsync
              // Index 13: FrameCompleted
              if let (Ok(vsync), Ok(completed)) = (parts[1].parse::<u64>(), parts[13].parse::<u64>()) {
-                 // Check logical validity: completed != 0
-                 if completed > 0 { 
+                 // Drop incomplete frames (FrameCompleted == 0) before computing anything.
+                 if completed > 0 {
                      intended_vsyncs.push(vsync);
+                     render_times_ms.push(completed.saturating_sub(vsync) as f32 / 1_000_000.0);
                  }
              }
         }
     }
-    
+
     if intended_vsyncs.is_empty() {
-        return None; 
+        return None;
     }
 
+    let frame_count = render_times_ms.len() as u32;
+    let frame_budget_ms = 1000.0 / refresh_hz;
+    let janky_frames = render_times_ms.iter().filter(|&&t| t as f64 > frame_budget_ms).count();
+    let jank_percent = janky_frames as f32 / frame_count as f32 * 100.0;
+
+    let mut sorted_render_times_ms = render_times_ms.clone();
+    sorted_render_times_ms.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let p50_ms = percentile_ms(&sorted_render_times_ms, 50.0);
+    let p90_ms = percentile_ms(&sorted_render_times_ms, 90.0);
+    let p99_ms = percentile_ms(&sorted_render_times_ms, 99.0);
+
     // 1. Check for Idleness
     // If the last frame happened more than 0.5s ago, the app is likely not animating, so FPS is effectively 0.
     // Use a threshold of 500ms (500,000,000 ns).
     let last_vsync = *intended_vsyncs.last().unwrap();
-    if uptime_ns > 0 {
-        if uptime_ns > last_vsync && (uptime_ns - last_vsync) > 500_000_000 {
-            return Some(0);
-        }
+    if uptime_ns > 0 && uptime_ns > last_vsync && (uptime_ns - last_vsync) > 500_000_000 {
+        return Some(FrameStats {
+            fps: 0,
+            jank_percent,
+            p50_ms,
+            p90_ms,
+            p99_ms,
+            frame_count,
+        });
     }
 
     // 2. Calculate FPS from the window
     // FPS = (Frame Count - 1) / (Last Frame Time - First Frame Time)
-    if intended_vsyncs.len() > 1 {
+    let fps = if intended_vsyncs.len() > 1 {
         let start = intended_vsyncs[0];
         let end = last_vsync;
-        
+
         if end > start {
-            let duration_ns = end - start;
-            let duration_sec = duration_ns as f64 / 1_000_000_000.0;
-            
+            let duration_sec = (end - start) as f64 / 1_000_000_000.0;
             if duration_sec > 0.0 {
                 let count = intended_vsyncs.len() as f64 - 1.0;
-                let fps = count / duration_sec;
-                return Some(fps.round() as u32);
+                (count / duration_sec).round() as u32
+            } else {
+                0
             }
+        } else {
+            0
         }
-    }
-    
-    // If we have frames but can't ensure duration > 0, fallback
-    None
+    } else {
+        0
+    };
+
+    Some(FrameStats {
+        fps,
+        jank_percent,
+        p50_ms,
+        p90_ms,
+        p99_ms,
+        frame_count,
+    })
 }
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_channel::{bounded, Receiver, Sender};
+use async_io::Timer;
+use futures_lite::FutureExt;
+use tauri::{AppHandle, Emitter, State};
+
+use super::stats::{get_device_stats, DeviceStats};
+
+// How many samples of each metric to keep for charting; old samples fall off the front
+// so a long-running monitor session doesn't grow unbounded.
+const HISTORY_CAP: usize = 300;
+
+// Battery barely moves between ticks, so its level is recorded into history only on
+// every BATTERY_SAMPLE_EVERY-th tick, while CPU/RAM/FPS are recorded every tick.
+// `get_device_stats` still reads the battery on every tick, and each emitted event
+// carries it.
+const BATTERY_SAMPLE_EVERY: u32 = 10;
+
+/// Rolling per-metric history for one device, returned by `get_stats_history` for
+/// charting. `get_device_stats` itself already delta-samples CPU/network sub-second, so
+/// this just keeps the last `HISTORY_CAP` values of each series as they arrive.
+#[derive(Default, Clone, serde::Serialize)]
+pub struct History {
+    pub cpu_usage: Vec<f32>,
+    pub ram_used: Vec<u64>,
+    pub battery_level: Vec<u8>,
+    pub fps: Vec<u32>,
+}
+
+impl History {
+    fn push(&mut self, stats: &DeviceStats, sample_battery: bool) {
+        push_capped(&mut self.cpu_usage, stats.cpu_usage);
+        push_capped(&mut self.ram_used, stats.ram_used);
+        if sample_battery {
+            push_capped(&mut self.battery_level, stats.battery.level);
+        }
+        if let Some(app) = &stats.app_stats {
+            push_capped(&mut self.fps, app.frame_stats.fps);
+        }
+    }
+}
+
+fn push_capped<T>(buf: &mut Vec<T>, value: T) {
+    buf.push(value);
+    if buf.len() > HISTORY_CAP {
+        buf.remove(0);
+    }
+}
+
+/// One monitored device's background sampler. Mirrors `LogcatProcess`: the actual loop
+/// lives entirely inside the task spawned by `start_stats_monitor`, and this just holds
+/// the channel `stop_stats_monitor` uses to signal it.
+struct StatsMonitor {
+    stop_tx: Sender<()>,
+}
+
+pub struct StatsMonitorState(pub Mutex<HashMap<String, StatsMonitor>>);
+
+impl Default for StatsMonitorState {
+    fn default() -> Self {
+        StatsMonitorState(Mutex::new(HashMap::new()))
+    }
+}
+
+pub struct StatsHistoryState(pub Arc<Mutex<HashMap<String, History>>>);
+
+impl Default for StatsHistoryState {
+    fn default() -> Self {
+        StatsHistoryState(Arc::new(Mutex::new(HashMap::new())))
+    }
+}
+
+#[tauri::command]
+pub fn start_stats_monitor(
+    app: AppHandle,
+    monitor_state: State<'_, StatsMonitorState>,
+    history_state: State<'_, StatsHistoryState>,
+    device: String,
+    package: Option<String>,
+    interval_ms: Option<u64>,
+) -> Result<String, String> {
+    let mut monitors = monitor_state.0.lock().map_err(|e| e.to_string())?;
+    if monitors.contains_key(&device) {
+        return Ok("Stats monitor already running".to_string());
+    }
+
+    let interval = Duration::from_millis(interval_ms.unwrap_or(1000));
+    // Capacity 1: only one stop signal is ever meaningful, mirroring start_logcat.
+    let (stop_tx, stop_rx) = bounded(1);
+
+    history_state
+        .0
+        .lock()
+        .map_err(|e| e.to_string())?
+        .entry(device.clone())
+        .or_default();
+
+    tauri::async_runtime::spawn(run_monitor(
+        app,
+        device.clone(),
+        package,
+        interval,
+        history_state.0.clone(),
+        stop_rx,
+    ));
+
+    monitors.insert(device, StatsMonitor { stop_tx });
+    Ok("Stats monitor started".to_string())
+}
+
+/// Owns one device's sampling loop until `stop_rx` fires: sample, record into history,
+/// emit `device-stats://{serial}`, then race the next tick's wait against a stop signal
+/// so the loop reacts the instant `stop_stats_monitor` is called instead of waiting out
+/// the rest of `interval`.
+async fn run_monitor(
+    app: AppHandle,
+    device: String,
+    package: Option<String>,
+    interval: Duration,
+    history: Arc<Mutex<HashMap<String, History>>>,
+    stop_rx: Receiver<()>,
+) {
+    let mut tick: u32 = 0;
+
+    loop {
+        if stop_rx.try_recv().is_ok() {
+            return;
+        }
+
+        let sample_battery = tick % BATTERY_SAMPLE_EVERY == 0;
+        tick = tick.wrapping_add(1);
+
+        if let Ok(stats) = get_device_stats(device.clone(), package.clone(), None).await {
+            if let Ok(mut map) = history.lock() {
+                map.entry(device.clone()).or_default().push(&stats, sample_battery);
+            }
+            let _ = app.emit(&format!("device-stats://{}", device), stats);
+        }
+
+        let still_running = Timer::after(interval)
+            .map(|_| true)
+            .or(async {
+                let _ = stop_rx.recv().await;
+                false
+            })
+            .await;
+        if !still_running {
+            return;
+        }
+    }
+}
+
+#[tauri::command]
+pub fn stop_stats_monitor(
+    monitor_state: State<'_, StatsMonitorState>,
+    device: String,
+) -> Result<String, String> {
+    let mut monitors = monitor_state.0.lock().map_err(|e| e.to_string())?;
+
+    if let Some(monitor) = monitors.remove(&device) {
+        // The monitor task is parked in its `.or()` race, so this wakes it at the next
+        // poll instead of it waiting out the rest of the interval.
+        let _ = monitor.stop_tx.try_send(());
+        return Ok("Stats monitor stopping".to_string());
+    }
+
+    Ok("Stats monitor not running".to_string())
+}
+
+#[tauri::command]
+pub fn is_stats_monitor_active(
+    monitor_state: State<'_, StatsMonitorState>,
+    device: String,
+) -> Result<bool, String> {
+    let monitors = monitor_state.0.lock().map_err(|e| e.to_string())?;
+    Ok(monitors.contains_key(&device))
+}
+
+#[tauri::command]
+pub fn get_stats_history(
+    history_state: State<'_, StatsHistoryState>,
+    device: String,
+) -> Result<History, String> {
+    let history = history_state.0.lock().map_err(|e| e.to_string())?;
+    Ok(history.get(&device).cloned().unwrap_or_default())
+}
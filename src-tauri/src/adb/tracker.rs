@@ -0,0 +1,192 @@
+use std::process::{Command, Child, Stdio};
+use std::io::{BufReader, Read};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tauri::{command, AppHandle, Emitter, Manager, State};
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+// Reconnect backoff bounds used when the adb server dies out from under the monitor.
+const BACKOFF_START: Duration = Duration::from_millis(500);
+const BACKOFF_MAX: Duration = Duration::from_secs(5);
+
+/// Background monitor over `adb track-devices`. A single supervisor thread owns the
+/// long-lived child and reconnects with backoff whenever the stream ends, so the
+/// monitor survives the adb server being bounced by `restart_adb_server`.
+pub struct DeviceMonitorState {
+    should_stop: Arc<AtomicBool>,
+    running: Arc<AtomicBool>,
+    child: Arc<Mutex<Option<Child>>>,
+}
+
+impl Default for DeviceMonitorState {
+    fn default() -> Self {
+        Self {
+            should_stop: Arc::new(AtomicBool::new(false)),
+            running: Arc::new(AtomicBool::new(false)),
+            child: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+#[derive(serde::Serialize, Clone)]
+struct DeviceEvent {
+    serial: String,
+    state: String,
+}
+
+/// Start the device monitor. Idempotent: a monitor already running is left alone
+/// (the supervisor reconnects on its own), so this is safe to call at app launch and
+/// again from `restart_adb_server`.
+#[command]
+pub fn start_device_monitor(app: AppHandle, state: State<'_, DeviceMonitorState>) -> Result<(), String> {
+    if state.running.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+    state.should_stop.store(false, Ordering::SeqCst);
+
+    let should_stop = state.should_stop.clone();
+    let running = state.running.clone();
+    let child_slot = state.child.clone();
+    let app_handle = app.clone();
+
+    thread::spawn(move || {
+        let mut previous: HashMap<String, String> = HashMap::new();
+        let mut backoff = BACKOFF_START;
+
+        while !should_stop.load(Ordering::SeqCst) {
+            match spawn_tracker() {
+                Ok(mut child) => {
+                    backoff = BACKOFF_START; // reset after a successful connect
+                    let stdout = child.stdout.take();
+                    *child_slot.lock().unwrap() = Some(child);
+
+                    if let Some(stdout) = stdout {
+                        read_frames(&app_handle, stdout, &mut previous, &should_stop);
+                    }
+
+                    // Stream ended (server died / EOF). Drop the handle and reconnect.
+                    *child_slot.lock().unwrap() = None;
+                }
+                Err(_) => {}
+            }
+
+            if should_stop.load(Ordering::SeqCst) {
+                break;
+            }
+            thread::sleep(backoff);
+            backoff = (backoff * 2).min(BACKOFF_MAX);
+        }
+
+        running.store(false, Ordering::SeqCst);
+    });
+
+    Ok(())
+}
+
+#[command]
+pub fn stop_device_monitor(state: State<'_, DeviceMonitorState>) -> Result<(), String> {
+    state.should_stop.store(true, Ordering::SeqCst);
+    if let Some(mut child) = state.child.lock().map_err(|e| e.to_string())?.take() {
+        let _ = child.kill();
+    }
+    Ok(())
+}
+
+fn spawn_tracker() -> std::io::Result<Child> {
+    let mut cmd = Command::new("adb");
+    cmd.arg("track-devices");
+    cmd.stdout(Stdio::piped()).stderr(Stdio::null());
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    cmd.spawn()
+}
+
+/// Read the length-prefixed frames adb pushes on every device-set change. Each frame
+/// is a 4 hex-digit byte length followed by the full `serial\tstate` device list.
+fn read_frames<R: Read>(
+    app: &AppHandle,
+    stdout: R,
+    previous: &mut HashMap<String, String>,
+    should_stop: &Arc<AtomicBool>,
+) {
+    let mut reader = BufReader::new(stdout);
+
+    loop {
+        if should_stop.load(Ordering::SeqCst) {
+            break;
+        }
+
+        let mut len_buf = [0u8; 4];
+        if reader.read_exact(&mut len_buf).is_err() {
+            break; // EOF: server gone
+        }
+        let len = match std::str::from_utf8(&len_buf)
+            .ok()
+            .and_then(|s| usize::from_str_radix(s, 16).ok())
+        {
+            Some(l) => l,
+            None => break,
+        };
+
+        let mut payload = vec![0u8; len];
+        if reader.read_exact(&mut payload).is_err() {
+            break;
+        }
+
+        let snapshot = parse_snapshot(&String::from_utf8_lossy(&payload));
+        diff_snapshots(app, previous, &snapshot);
+        *previous = snapshot;
+    }
+}
+
+// Parse a device-list payload into serial -> state pairs.
+fn parse_snapshot(text: &str) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let mut parts = trimmed.split_whitespace();
+        if let (Some(serial), Some(state)) = (parts.next(), parts.next()) {
+            map.insert(serial.to_string(), state.to_string());
+        }
+    }
+    map
+}
+
+fn diff_snapshots(app: &AppHandle, previous: &HashMap<String, String>, current: &HashMap<String, String>) {
+    for (serial, state) in current {
+        match previous.get(serial) {
+            None => {
+                let _ = app.emit("device-connected", DeviceEvent { serial: serial.clone(), state: state.clone() });
+            }
+            Some(old) if old != state => {
+                let _ = app.emit("device-state-changed", DeviceEvent { serial: serial.clone(), state: state.clone() });
+            }
+            _ => {}
+        }
+    }
+    for (serial, state) in previous {
+        if !current.contains_key(serial) {
+            let _ = app.emit("device-disconnected", DeviceEvent { serial: serial.clone(), state: state.clone() });
+        }
+    }
+}
+
+/// Tear the monitor down on app exit.
+pub fn shutdown_device_monitor(app: &AppHandle) {
+    if let Some(state) = app.try_state::<DeviceMonitorState>() {
+        state.should_stop.store(true, Ordering::SeqCst);
+        if let Ok(mut guard) = state.child.lock() {
+            if let Some(mut child) = guard.take() {
+                let _ = child.kill();
+            }
+        }
+    }
+}
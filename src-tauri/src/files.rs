@@ -1,17 +1,139 @@
 use std::fs;
+use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 use tauri::command;
 
+// Coarse classification of a directory entry, derived from its extension and, for
+// images, a cheap magic-byte sniff. Mirrors the buckets czkawka's scanner groups files
+// into, so the UI can pick an icon/preview without re-deriving this from the name.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileType {
+    Xml,
+    Html,
+    Image,
+    Archive,
+    Log,
+    Screenshot,
+    Other,
+    Directory,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FileEntry {
     name: String,
     path: String,
     is_dir: bool,
+    size: u64,
+    modified_date: u64,
+    file_type: FileType,
+    // Set when the entry looks obviously corrupt (zero-byte screenshot, magic bytes
+    // that don't match the extension, a truncated archive); `None` when it looks fine.
+    #[serde(default)]
+    error_string: Option<String>,
+}
+
+// First few bytes of common image formats, used to catch a file that was renamed,
+// truncated, or never finished writing.
+fn sniff_image_magic(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(&[0x89, 0x50, 0x4E, 0x47]) {
+        Some("png")
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("jpeg")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("gif")
+    } else if bytes.starts_with(b"BM") {
+        Some("bmp")
+    } else if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some("webp")
+    } else {
+        None
+    }
+}
+
+// First few bytes of common archive formats, used to detect a truncated download.
+fn sniff_archive_magic(bytes: &[u8]) -> bool {
+    bytes.starts_with(&[0x50, 0x4B, 0x03, 0x04]) // zip (and jar/apk, which are zips)
+        || bytes.starts_with(&[0x1F, 0x8B]) // gzip
+        || bytes.starts_with(b"7z\xBC\xAF\x27\x1C")
+        || bytes.get(257..262) == Some(b"ustar".as_slice()) // tar (ustar header field)
+}
+
+// Classify an entry by extension, then sanity-check image/screenshot/archive files
+// against their own bytes. Returns the classification plus a human-readable reason
+// when the file looks corrupt.
+fn classify_file(path: &Path, size: u64) -> (FileType, Option<String>) {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    let is_screenshot = stem.contains("screenshot") || stem.contains("screen_record") || stem.contains("recording");
+
+    let file_type = match ext.as_str() {
+        "xml" => FileType::Xml,
+        "html" | "htm" => FileType::Html,
+        "png" | "jpg" | "jpeg" | "gif" | "bmp" | "webp" => {
+            if is_screenshot {
+                FileType::Screenshot
+            } else {
+                FileType::Image
+            }
+        }
+        "mp4" | "webm" | "avi" | "mkv" if is_screenshot => FileType::Screenshot,
+        "zip" | "apk" | "gz" | "7z" | "tar" => FileType::Archive,
+        "log" | "txt" => FileType::Log,
+        _ => FileType::Other,
+    };
+
+    let is_image_like = matches!(file_type, FileType::Image | FileType::Screenshot)
+        && matches!(ext.as_str(), "png" | "jpg" | "jpeg" | "gif" | "bmp" | "webp");
+
+    if (file_type == FileType::Screenshot || is_image_like) && size == 0 {
+        return (file_type, Some("Corrupt: file is empty".to_string()));
+    }
+
+    if is_image_like {
+        if let Ok(bytes) = fs::read(path).map(|b| b.into_iter().take(16).collect::<Vec<u8>>()) {
+            if let Some(sniffed) = sniff_image_magic(&bytes) {
+                let ext_matches = match sniffed {
+                    "jpeg" => ext == "jpg" || ext == "jpeg",
+                    other => ext == other,
+                };
+                if !ext_matches {
+                    return (
+                        file_type,
+                        Some(format!("Corrupt: .{} extension but {} magic bytes", ext, sniffed)),
+                    );
+                }
+            } else if !bytes.is_empty() {
+                return (file_type, Some("Corrupt: unrecognized image header".to_string()));
+            }
+        }
+    }
+
+    if file_type == FileType::Archive {
+        if size == 0 {
+            return (file_type, Some("Corrupt: archive is empty".to_string()));
+        }
+        if let Ok(bytes) = fs::read(path).map(|b| b.into_iter().take(262).collect::<Vec<u8>>()) {
+            if !sniff_archive_magic(&bytes) {
+                return (file_type, Some("Corrupt: missing archive magic bytes".to_string()));
+            }
+        }
+    }
+
+    (file_type, None)
 }
 
 #[command]
-pub fn list_directory(path: Option<String>) -> Result<Vec<FileEntry>, String> {
+pub fn list_directory(path: Option<String>, sort_by: Option<String>) -> Result<Vec<FileEntry>, String> {
     let target_path = if let Some(p) = path {
         if p.is_empty() {
             ".".to_string()
@@ -36,23 +158,54 @@ pub fn list_directory(path: Option<String>) -> Result<Vec<FileEntry>, String> {
             continue;
         }
 
+        let is_dir = metadata.is_dir();
+        let size = metadata.len();
+        let modified_date = metadata
+            .modified()
+            .ok()
+            .and_then(|m| m.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        let (file_type, error_string) = if is_dir {
+            (FileType::Directory, None)
+        } else {
+            classify_file(&path_buf, size)
+        };
+
         entries.push(FileEntry {
             name,
             path: path_buf.to_string_lossy().to_string(),
-            is_dir: metadata.is_dir(),
+            is_dir,
+            size,
+            modified_date,
+            file_type,
+            error_string,
         });
     }
 
-    // Sort: Dirs first, then files
-    entries.sort_by(|a, b| {
-        if a.is_dir && !b.is_dir {
-            std::cmp::Ordering::Less
-        } else if !a.is_dir && b.is_dir {
-            std::cmp::Ordering::Greater
-        } else {
-            a.name.to_lowercase().cmp(&b.name.to_lowercase())
-        }
-    });
+    // Dirs always sort first; within each group, order by the requested key.
+    match sort_by.as_deref() {
+        Some("size") => entries.sort_by(|a, b| match (a.is_dir, b.is_dir) {
+            (true, false) => std::cmp::Ordering::Less,
+            (false, true) => std::cmp::Ordering::Greater,
+            _ => b.size.cmp(&a.size),
+        }),
+        Some("modified") => entries.sort_by(|a, b| match (a.is_dir, b.is_dir) {
+            (true, false) => std::cmp::Ordering::Less,
+            (false, true) => std::cmp::Ordering::Greater,
+            _ => b.modified_date.cmp(&a.modified_date),
+        }),
+        _ => entries.sort_by(|a, b| {
+            if a.is_dir && !b.is_dir {
+                std::cmp::Ordering::Less
+            } else if !a.is_dir && b.is_dir {
+                std::cmp::Ordering::Greater
+            } else {
+                a.name.to_lowercase().cmp(&b.name.to_lowercase())
+            }
+        }),
+    }
 
     Ok(entries)
 }
@@ -84,6 +237,14 @@ pub fn read_file(path: String) -> Result<String, String> {
 #[command]
 pub fn save_image(path: String, content: Vec<u8>) -> Result<(), String> {
     use std::io::Write;
+
+    if content.is_empty() {
+        return Err("Refusing to save an empty image".to_string());
+    }
+    if sniff_image_magic(&content).is_none() {
+        return Err("Refusing to save: content is not a recognized image format".to_string());
+    }
+
     let mut file = fs::File::create(&path).map_err(|e| e.to_string())?;
     file.write_all(&content).map_err(|e| e.to_string())?;
     Ok(())
@@ -1,7 +1,7 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 use serde::{Serialize, Deserialize};
-use tauri::command;
+use tauri::{command, AppHandle, Emitter};
 use regex::Regex;
 use std::process::Command;
 
@@ -17,13 +17,152 @@ pub struct TestLog {
     duration: String,
     xml_path: String,
     log_html_path: String,
+    // Invalidation signature of the source output.xml, captured when this entry was
+    // parsed. `#[serde(default)]` keeps old caches loadable; a missing signature just
+    // forces a re-parse on the next scan.
+    #[serde(default)]
+    size: u64,
+    #[serde(default)]
+    mtime_secs: u64,
+    #[serde(default)]
+    mtime_nanos: u32,
+    // Cheap content digest, consulted only when the mtime is too coarse to trust.
+    #[serde(default)]
+    digest: String,
+    // Set when the run's output.xml is truncated/malformed/incomplete. `status` is then
+    // "BROKEN" and this carries a human-readable reason; `None` for healthy runs.
+    #[serde(default)]
+    error_string: Option<String>,
 }
 
+// Self-describing header wrapped around the cached payload so schema changes are
+// detectable. A cache whose magic or format version doesn't match the current build is
+// discarded and rebuilt rather than deserialized into wrongly-defaulted entries.
+const CACHE_MAGIC: &str = "ROBOT_RUNNER_HISTORY";
+const CACHE_FORMAT_VERSION: u32 = 2;
+
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    magic: String,
+    format_version: u32,
+    // Crate version that wrote the cache, for diagnostics and future migrations.
+    writer_version: String,
+    logs: Vec<TestLog>,
+}
+
+// Size + high-resolution mtime of a file, used to decide whether a cached entry is
+// still valid without re-parsing.
+fn file_signature(path: &Path) -> Option<(u64, u64, u32)> {
+    let meta = fs::metadata(path).ok()?;
+    let modified = meta.modified().ok()?;
+    let dur = modified.duration_since(std::time::UNIX_EPOCH).ok()?;
+    Some((meta.len(), dur.as_secs(), dur.subsec_nanos()))
+}
+
+// Inspect a run's output.xml (plus its sibling reports) and classify how complete it
+// is. Runs that crash, get killed, or are interrupted leave a truncated or malformed
+// file; rather than reporting those as a clean "0 P / 0 F" pass we flag them so the UI
+// can offer a re-run. Returns `None` when healthy, or a reason string otherwise.
+fn classify_broken(content: &str, folder_path: &Path) -> Option<String> {
+    if content.trim().is_empty() {
+        return Some("Malformed: output.xml is empty".to_string());
+    }
+    if !content.contains("<robot") {
+        return Some("Malformed: missing <robot> root element".to_string());
+    }
+    // Well-formed runs close the root tag at EOF; a missing one means truncation.
+    if !content.trim_end().ends_with("</robot>") {
+        return Some("Truncated: missing closing </robot> tag".to_string());
+    }
+    // The aggregate stat is written last, so its absence means the run never finished.
+    let re_stat = Regex::new(r#"<stat[^>]*>All Tests</stat>"#).ok();
+    if re_stat.map(|re| !re.is_match(content)).unwrap_or(false) {
+        return Some("Incomplete: missing 'All Tests' statistics".to_string());
+    }
+    // Robot writes log.html/report.html after output.xml; neither means an aborted run.
+    let has_report = folder_path.join("log.html").exists() || folder_path.join("report.html").exists();
+    if !has_report {
+        return Some("Incomplete: no log.html/report.html generated".to_string());
+    }
+    None
+}
+
+// Cheap, dependency-free content digest over the XML bytes. Only used to break
+// same-second mtime ties, so collision resistance isn't a concern here.
+fn content_digest(bytes: &[u8]) -> String {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+// Default freshness window: cached history newer than this is served as-is; older than
+// this is still served immediately but triggers a background revalidation.
+const DEFAULT_TTL_SECS: u64 = 30;
+
 #[command]
-pub fn get_test_tests_history(custom_path: Option<String>, refresh: Option<bool>) -> Result<Vec<TestLog>, String> {
+pub fn get_test_tests_history(
+    app: AppHandle,
+    custom_path: Option<String>,
+    refresh: Option<bool>,
+    ttl_secs: Option<u64>,
+    force: Option<bool>,
+) -> Result<Vec<TestLog>, String> {
+    // `force` (or legacy `refresh`) means a true rebuild: scan synchronously, ignoring
+    // any cached entries.
+    if force.unwrap_or(false) || refresh.unwrap_or(false) {
+        return scan_history(custom_path, true);
+    }
+
+    // Stale-while-revalidate: within the TTL the cache is fresh and returned directly;
+    // past the TTL it's stale-but-usable, so we hand it back instantly and kick off the
+    // rescan on a background task that emits `history-updated` when fresh data is ready.
+    match load_cached_logs_with_age(&custom_path) {
+        Some((logs, age)) if !logs.is_empty() => {
+            let ttl = std::time::Duration::from_secs(ttl_secs.unwrap_or(DEFAULT_TTL_SECS));
+            if age <= ttl {
+                return Ok(logs);
+            }
+            let bg_path = custom_path.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Ok(fresh) = scan_history(bg_path, false) {
+                    let _ = app.emit("history-updated", fresh);
+                }
+            });
+            Ok(logs)
+        }
+        // No usable cache: the first paint has to block on a full scan.
+        _ => scan_history(custom_path, false),
+    }
+}
+
+// Read the persisted history cache and report how long ago it was written. Returns
+// `None` when there's no parseable, version-matching cache to serve.
+fn load_cached_logs_with_age(custom_path: &Option<String>) -> Option<(Vec<TestLog>, std::time::Duration)> {
+    let mut candidates = vec![PathBuf::from("../test_results"), PathBuf::from("test_results")];
+    if let Some(path) = custom_path {
+        if !path.is_empty() {
+            candidates.insert(0, PathBuf::from(path));
+        }
+    }
+    let primary_dir = candidates.iter().find(|p| p.exists() && p.is_dir())?;
+    let cache_path = primary_dir.join("history_cache.json");
+
+    let meta = fs::metadata(&cache_path).ok()?;
+    let age = meta.modified().ok()?.elapsed().unwrap_or_default();
+
+    let content = fs::read_to_string(&cache_path).ok()?;
+    let cache = serde_json::from_str::<CacheFile>(&content).ok()?;
+    if cache.magic != CACHE_MAGIC || cache.format_version != CACHE_FORMAT_VERSION {
+        return None;
+    }
+    Some((cache.logs, age))
+}
+
+fn scan_history(custom_path: Option<String>, force: bool) -> Result<Vec<TestLog>, String> {
     // Assumption: logs are in "../test_results" relative to the app execution
     // Or we can assume a fixed path. For now, let's look at the project root "test_results".
-    
+
     let mut candidates = vec![
         PathBuf::from("../test_results"),
         PathBuf::from("test_results"),
@@ -39,48 +178,55 @@ pub fn get_test_tests_history(custom_path: Option<String>, refresh: Option<bool>
     // Identify the primary log directory (first valid one) to store cache
     let primary_dir = candidates.iter().find(|p| p.exists() && p.is_dir());
     let cache_file = primary_dir.map(|p| p.join("history_cache.json"));
-    
-    let force_refresh = refresh.unwrap_or(false);
+
+    let force_refresh = force;
 
     // Cache State
     let mut cache_map: std::collections::HashMap<String, TestLog> = std::collections::HashMap::new();
     let mut cache_mtime = std::time::SystemTime::UNIX_EPOCH;
+    // Wall-clock second in which the cache was last written. Any source file touched in
+    // this same second has an mtime we cannot trust (1-second filesystem granularity),
+    // so those entries fall back to a content-digest comparison.
+    let mut cache_write_secs: u64 = 0;
 
-    // Always try to load cache first to build the map, even if force_refresh is true?
-    // Actually if force_refresh is true, we might want to re-parse everything regardless of mtime.
-    // But the user asked for "only new metadata", effectively "incremental update".
-    // So "Refresh" button should probably behave as "Scan for changes".
-    // True "Force Rebuild" might be a separate concern, but for now assuming "Refresh" = "Incremental Update".
-
+    // Load the cache unconditionally; under `force_refresh`, `cache_map` is emptied
+    // afterwards so every run below is treated as a cache miss and re-parsed.
     if let Some(ref cache_path) = cache_file {
         if cache_path.exists() {
             if let Ok(metadata) = fs::metadata(cache_path) {
                 if let Ok(modified) = metadata.modified() {
                     cache_mtime = modified;
+                    if let Ok(dur) = modified.duration_since(std::time::UNIX_EPOCH) {
+                        cache_write_secs = dur.as_secs();
+                    }
                 }
             }
 
             println!("Loading logs from cache: {:?}", cache_path);
             if let Ok(content) = fs::read_to_string(cache_path) {
-                if let Ok(cached_logs) = serde_json::from_str::<Vec<TestLog>>(&content) {
-                    for log in cached_logs {
-                        // Use xml_path as unique key
-                        cache_map.insert(log.xml_path.clone(), log);
+                match serde_json::from_str::<CacheFile>(&content) {
+                    Ok(cache) if cache.magic == CACHE_MAGIC && cache.format_version == CACHE_FORMAT_VERSION => {
+                        for log in cache.logs {
+                            // Use xml_path as unique key
+                            cache_map.insert(log.xml_path.clone(), log);
+                        }
+                    }
+                    Ok(_) => {
+                        // Magic/version mismatch: shape may differ, so rebuild from scratch.
+                        println!("Cache format mismatch, rebuilding history from scan.");
+                    }
+                    Err(_) => {
+                        println!("Failed to parse cache, falling back to full scan.");
                     }
-                } else {
-                    println!("Failed to parse cache, falling back to full scan.");
                 }
             }
         }
     }
     
-    // If forcing complete re-parse (ignoring timestamps), we could clear cache_map here.
-    // But "refresh" usually means "check for new stuff".
+    // A forced refresh ignores the incremental size/mtime/digest gating entirely:
+    // drop every cached entry so the scan below re-parses every output.xml from disk.
     if force_refresh {
-         // Maybe user WANTS to re-parse modified files even if timestamp logic fails? 
-         // For now, let's trust mtime. If force_refresh is true, we still use cache if file unmodified.
-         // If we strictly want to invalid cache, we would reset cache_mtime to UNIX_EPOCH.
-         // Let's assume standard incremental behavior.
+        cache_map.clear();
     }
 
     let mut logs = Vec::new();
@@ -108,20 +254,31 @@ pub fn get_test_tests_history(custom_path: Option<String>, refresh: Option<bool>
                     let xml_path_str = xml_path.to_string_lossy().to_string();
                     let parent = xml_path.parent().unwrap_or(Path::new(""));
 
-                    // Check mtime
+                    // Decide reuse from size + high-resolution mtime, falling back to a
+                    // content digest when the mtime lands in the cache-write second and
+                    // therefore can't be trusted.
                     let mut use_cache = false;
                     if let Some(cached_log) = cache_map.get(&xml_path_str) {
-                         if let Ok(meta) = fs::metadata(xml_path) {
-                             if let Ok(modified) = meta.modified() {
-                                 // If XML file is OLDER than cache file, assume it hasn't changed since cache was written.
-                                 // Adding a small buffer or just strict comparison.
-                                 // If modified <= cache_mtime: reuse
-                                 if modified <= cache_mtime {
-                                     use_cache = true;
-                                     logs.push(cached_log.clone());
-                                 }
-                             }
-                         }
+                        if let Some((size, secs, nanos)) = file_signature(xml_path) {
+                            let size_match = size == cached_log.size;
+                            let mtime_match = secs == cached_log.mtime_secs && nanos == cached_log.mtime_nanos;
+                            let ambiguous = secs == cache_write_secs;
+
+                            if size_match && mtime_match && !ambiguous {
+                                use_cache = true;
+                            } else if size_match && ambiguous && !cached_log.digest.is_empty() {
+                                // mtime is untrustworthy here: confirm with the digest.
+                                if let Ok(bytes) = fs::read(xml_path) {
+                                    if content_digest(&bytes) == cached_log.digest {
+                                        use_cache = true;
+                                    }
+                                }
+                            }
+
+                            if use_cache {
+                                logs.push(cached_log.clone());
+                            }
+                        }
                     }
 
                     if !use_cache {
@@ -141,7 +298,13 @@ pub fn get_test_tests_history(custom_path: Option<String>, refresh: Option<bool>
 
     // 2. Save new cache (Atomically if possible, but standard write is fine)
     if let Some(ref cache_path) = cache_file {
-        if let Ok(json) = serde_json::to_string_pretty(&logs) {
+        let cache = CacheFile {
+            magic: CACHE_MAGIC.to_string(),
+            format_version: CACHE_FORMAT_VERSION,
+            writer_version: env!("CARGO_PKG_VERSION").to_string(),
+            logs: logs.clone(),
+        };
+        if let Ok(json) = serde_json::to_string_pretty(&cache) {
             let _ = fs::write(cache_path, json);
             println!("Saved logs cache to: {:?}", cache_path);
         }
@@ -150,6 +313,15 @@ pub fn get_test_tests_history(custom_path: Option<String>, refresh: Option<bool>
     Ok(logs)
 }
 
+#[command]
+pub fn list_broken_runs(custom_path: Option<String>, refresh: Option<bool>) -> Result<Vec<TestLog>, String> {
+    // Reuse the synchronous history scan (re-parsing every run only when `refresh` is
+    // set) and keep only the runs flagged BROKEN, so users can re-run or clean them up
+    // from a dedicated view.
+    let logs = scan_history(custom_path, refresh.unwrap_or(false))?;
+    Ok(logs.into_iter().filter(|l| l.status == "BROKEN").collect())
+}
+
 fn parse_log_entry(folder_path: &Path, xml_path: &Path) -> Option<TestLog> {
     let content = fs::read_to_string(xml_path).ok()?;
     let abs_folder_path = folder_path.canonicalize().unwrap_or(folder_path.to_path_buf());
@@ -231,7 +403,16 @@ fn parse_log_entry(folder_path: &Path, xml_path: &Path) -> Option<TestLog> {
         (0, 0)
     };
     
-    let status = if fail > 0 { "FAIL" } else { "PASS" }.to_string();
+    // A broken/incomplete artifact overrides the pass/fail verdict, which would
+    // otherwise read as a misleading clean pass.
+    let error_string = classify_broken(&content, folder_path);
+    let status = if error_string.is_some() {
+        "BROKEN".to_string()
+    } else if fail > 0 {
+        "FAIL".to_string()
+    } else {
+        "PASS".to_string()
+    };
 
     // Timestamp logic: Prefer metadata, fall back to XML
     let timestamp = if let Some(ts) = meta_timestamp {
@@ -246,8 +427,13 @@ fn parse_log_entry(folder_path: &Path, xml_path: &Path) -> Option<TestLog> {
 
     let log_html_path = abs_folder_path.join("log.html").to_string_lossy().to_string();
 
+    // Capture the invalidation signature alongside the parse so the next scan can skip
+    // re-reading this file when nothing has changed.
+    let (size, mtime_secs, mtime_nanos) = file_signature(xml_path).unwrap_or((0, 0, 0));
+    let digest = content_digest(content.as_bytes());
+
     Some(TestLog {
-        path: abs_folder_path.to_string_lossy().to_string(), 
+        path: abs_folder_path.to_string_lossy().to_string(),
         xml_path: xml_path.to_string_lossy().to_string(),
         suite_name,
         status,
@@ -256,10 +442,353 @@ fn parse_log_entry(folder_path: &Path, xml_path: &Path) -> Option<TestLog> {
         android_version,
         timestamp,
         duration: format!("{} P / {} F", pass, fail),
-        log_html_path
+        log_html_path,
+        size,
+        mtime_secs,
+        mtime_nanos,
+        digest,
+        error_string,
     })
 }
 
+// ---------------------------------------------------------------------------
+// Searchable catalog
+// ---------------------------------------------------------------------------
+//
+// Beyond one TestLog per output.xml, we keep a compact index of every individual
+// test case across all runs so the UI can answer "every run where test X failed" or
+// "all smoke runs on Android 13" without re-reading each (large) XML. The catalog is
+// persisted next to the history cache and rebuilt incrementally with the same
+// size+mtime+digest gating.
+
+const CATALOG_MAGIC: &str = "ROBOT_RUNNER_CATALOG";
+const CATALOG_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TestCaseEntry {
+    name: String,
+    suite: String,
+    tags: Vec<String>,
+    status: String,
+    // Owning run, keyed by its output.xml path, plus where to open its report.
+    xml_path: String,
+    log_html_path: String,
+    device_udid: Option<String>,
+    android_version: Option<String>,
+    // Byte offset of the test element within output.xml, used as a stable jump anchor.
+    log_offset: u64,
+}
+
+// One run's worth of catalog data, carrying the same invalidation signature as the
+// history cache so unchanged runs are reused wholesale.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct RunCatalog {
+    xml_path: String,
+    size: u64,
+    mtime_secs: u64,
+    mtime_nanos: u32,
+    digest: String,
+    tests: Vec<TestCaseEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CatalogFile {
+    magic: String,
+    format_version: u32,
+    writer_version: String,
+    runs: Vec<RunCatalog>,
+}
+
+// Query over the catalog. Every field is optional; the ones that are set must all match
+// (AND). Name, suite, tag, device and text are compared case-insensitively as
+// substrings; `status` must match exactly ignoring ASCII case, `android_version` exactly.
+#[derive(Debug, Deserialize, Default)]
+pub struct CatalogQuery {
+    #[serde(default)]
+    test_name: Option<String>,
+    #[serde(default)]
+    suite: Option<String>,
+    #[serde(default)]
+    tag: Option<String>,
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    device_udid: Option<String>,
+    #[serde(default)]
+    android_version: Option<String>,
+    // Free-text term matched against name, suite, and tags at once.
+    #[serde(default)]
+    text: Option<String>,
+}
+
+fn contains_ci(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(&needle.to_lowercase())
+}
+
+fn entry_matches(entry: &TestCaseEntry, query: &CatalogQuery) -> bool {
+    if let Some(n) = &query.test_name {
+        if !contains_ci(&entry.name, n) {
+            return false;
+        }
+    }
+    if let Some(s) = &query.suite {
+        if !contains_ci(&entry.suite, s) {
+            return false;
+        }
+    }
+    if let Some(t) = &query.tag {
+        if !entry.tags.iter().any(|tag| contains_ci(tag, t)) {
+            return false;
+        }
+    }
+    if let Some(st) = &query.status {
+        if !entry.status.eq_ignore_ascii_case(st) {
+            return false;
+        }
+    }
+    if let Some(udid) = &query.device_udid {
+        if entry.device_udid.as_deref().map(|d| contains_ci(d, udid)) != Some(true) {
+            return false;
+        }
+    }
+    if let Some(ver) = &query.android_version {
+        if entry.android_version.as_deref() != Some(ver.as_str()) {
+            return false;
+        }
+    }
+    if let Some(text) = &query.text {
+        let hit = contains_ci(&entry.name, text)
+            || contains_ci(&entry.suite, text)
+            || entry.tags.iter().any(|tag| contains_ci(tag, text));
+        if !hit {
+            return false;
+        }
+    }
+    true
+}
+
+#[command]
+pub fn search_test_history(
+    query: CatalogQuery,
+    custom_path: Option<String>,
+    refresh: Option<bool>,
+) -> Result<Vec<TestCaseEntry>, String> {
+    let catalog = load_or_build_catalog(custom_path, refresh.unwrap_or(false))?;
+    let results: Vec<TestCaseEntry> = catalog
+        .into_iter()
+        .flat_map(|run| run.tests)
+        .filter(|entry| entry_matches(entry, &query))
+        .collect();
+    Ok(results)
+}
+
+// Incrementally (re)build the catalog, reusing unchanged runs from the persisted cache
+// and re-parsing only the output.xml files that changed since the last build, unless
+// `force` asks for every run to be re-parsed regardless.
+fn load_or_build_catalog(custom_path: Option<String>, force: bool) -> Result<Vec<RunCatalog>, String> {
+    let mut candidates = vec![PathBuf::from("../test_results"), PathBuf::from("test_results")];
+    if let Some(path) = custom_path {
+        if !path.is_empty() {
+            candidates.insert(0, PathBuf::from(path));
+        }
+    }
+
+    let primary_dir = candidates.iter().find(|p| p.exists() && p.is_dir());
+    let cache_file = primary_dir.map(|p| p.join("catalog_cache.json"));
+
+    let mut cache_map: std::collections::HashMap<String, RunCatalog> = std::collections::HashMap::new();
+    let mut cache_write_secs: u64 = 0;
+
+    if let Some(ref cache_path) = cache_file {
+        if let Ok(meta) = fs::metadata(cache_path) {
+            if let Ok(modified) = meta.modified() {
+                if let Ok(dur) = modified.duration_since(std::time::UNIX_EPOCH) {
+                    cache_write_secs = dur.as_secs();
+                }
+            }
+        }
+        if let Ok(content) = fs::read_to_string(cache_path) {
+            if let Ok(cache) = serde_json::from_str::<CatalogFile>(&content) {
+                if cache.magic == CATALOG_MAGIC && cache.format_version == CATALOG_FORMAT_VERSION {
+                    for run in cache.runs {
+                        cache_map.insert(run.xml_path.clone(), run);
+                    }
+                }
+            }
+        }
+    }
+
+    // A forced refresh ignores the incremental size/mtime/digest gating entirely:
+    // drop every cached entry so the scan below re-parses every output.xml from disk.
+    if force {
+        cache_map.clear();
+    }
+
+    let mut runs = Vec::new();
+    let mut seen_paths = std::collections::HashSet::new();
+
+    for base_path in candidates {
+        let abs_base = base_path.canonicalize().unwrap_or(base_path.clone());
+        let abs_path_str = abs_base.to_string_lossy().to_string();
+        if seen_paths.contains(&abs_path_str) {
+            continue;
+        }
+        seen_paths.insert(abs_path_str);
+
+        if base_path.exists() && base_path.is_dir() {
+            let walker = walkdir::WalkDir::new(&base_path).min_depth(1).max_depth(5).follow_links(true);
+            for entry in walker.into_iter().filter_map(|e| e.ok()) {
+                let fname = entry.file_name().to_string_lossy();
+                if fname.starts_with("output") && fname.ends_with(".xml") {
+                    let xml_path = entry.path();
+                    let xml_path_str = xml_path.to_string_lossy().to_string();
+                    let parent = xml_path.parent().unwrap_or(Path::new(""));
+
+                    let mut reused = false;
+                    if let Some(cached) = cache_map.get(&xml_path_str) {
+                        if let Some((size, secs, nanos)) = file_signature(xml_path) {
+                            let size_match = size == cached.size;
+                            let mtime_match = secs == cached.mtime_secs && nanos == cached.mtime_nanos;
+                            let ambiguous = secs == cache_write_secs;
+                            if size_match && mtime_match && !ambiguous {
+                                reused = true;
+                            } else if size_match && ambiguous && !cached.digest.is_empty() {
+                                if let Ok(bytes) = fs::read(xml_path) {
+                                    if content_digest(&bytes) == cached.digest {
+                                        reused = true;
+                                    }
+                                }
+                            }
+                            if reused {
+                                runs.push(cached.clone());
+                            }
+                        }
+                    }
+
+                    if !reused {
+                        if let Some(run) = parse_run_catalog(parent, xml_path) {
+                            runs.push(run);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(ref cache_path) = cache_file {
+        let cache = CatalogFile {
+            magic: CATALOG_MAGIC.to_string(),
+            format_version: CATALOG_FORMAT_VERSION,
+            writer_version: env!("CARGO_PKG_VERSION").to_string(),
+            runs: runs.clone(),
+        };
+        if let Ok(json) = serde_json::to_string_pretty(&cache) {
+            let _ = fs::write(cache_path, json);
+        }
+    }
+
+    Ok(runs)
+}
+
+// Resolve a run's device udid / android version from its metadata.json, falling back to
+// the encoded folder name. A trimmed-down cousin of the lookup in parse_log_entry.
+fn catalog_device_meta(folder_path: &Path) -> (Option<String>, Option<String>) {
+    let mut device_udid = None;
+    let mut android_version = None;
+
+    let metadata_path = folder_path.join("metadata.json");
+    if let Ok(meta_content) = fs::read_to_string(&metadata_path) {
+        if let Ok(re) = Regex::new(r#""device_udid"\s*:\s*"([^"]+)""#) {
+            if let Some(caps) = re.captures(&meta_content) {
+                device_udid = caps.get(1).map(|m| m.as_str().to_string());
+            }
+        }
+        if let Ok(re) = Regex::new(r#""android_version"\s*:\s*"([^"]+)""#) {
+            if let Some(caps) = re.captures(&meta_content) {
+                android_version = caps.get(1).map(|m| m.as_str().to_string());
+            }
+        }
+    }
+
+    if let Some(name) = folder_path.parent().and_then(|p| p.file_name()).and_then(|n| n.to_str()) {
+        if name.starts_with('A') {
+            let parts: Vec<&str> = name.split('_').collect();
+            if parts.len() >= 3 {
+                if android_version.is_none() {
+                    android_version = Some(parts[0][1..].to_string());
+                }
+                if device_udid.is_none() {
+                    device_udid = Some(parts[2].to_string());
+                }
+            }
+        }
+    }
+
+    (device_udid, android_version)
+}
+
+// Parse the suite/test tree out of one output.xml into catalog entries. Robot's tests
+// aren't nested, so each test element runs from its `<test ...>` to the next `</test>`;
+// its suite is the nearest enclosing `<suite name=...>` by document position.
+fn parse_run_catalog(folder_path: &Path, xml_path: &Path) -> Option<RunCatalog> {
+    let content = fs::read_to_string(xml_path).ok()?;
+    let (device_udid, android_version) = catalog_device_meta(folder_path);
+    let abs_folder = folder_path.canonicalize().unwrap_or(folder_path.to_path_buf());
+    let log_html_path = abs_folder.join("log.html").to_string_lossy().to_string();
+    let xml_path_str = xml_path.to_string_lossy().to_string();
+
+    // Positions of suite openings, so each test can be attributed to its suite.
+    let re_suite = Regex::new(r#"<suite\b[^>]*?\sname="([^"]+)""#).ok()?;
+    let suite_spans: Vec<(usize, String)> = re_suite
+        .captures_iter(&content)
+        .map(|c| (c.get(0).unwrap().start(), c[1].to_string()))
+        .collect();
+
+    let re_test = Regex::new(r#"(?s)<test\b[^>]*?\sname="([^"]+)".*?</test>"#).ok()?;
+    let re_status = Regex::new(r#"<status\s+status="([^"]+)""#).ok()?;
+    let re_tag = Regex::new(r#"<tag>([^<]*)</tag>"#).ok()?;
+
+    let mut tests = Vec::new();
+    for caps in re_test.captures_iter(&content) {
+        let whole = caps.get(0).unwrap();
+        let block = whole.as_str();
+        let name = caps[1].to_string();
+
+        // The test's own status is the last <status> in its block.
+        let status = re_status
+            .captures_iter(block)
+            .last()
+            .map(|c| c[1].to_string())
+            .unwrap_or_else(|| "UNKNOWN".to_string());
+
+        let tags: Vec<String> = re_tag.captures_iter(block).map(|c| c[1].trim().to_string()).collect();
+
+        let suite = suite_spans
+            .iter()
+            .rev()
+            .find(|(pos, _)| *pos < whole.start())
+            .map(|(_, n)| n.clone())
+            .unwrap_or_else(|| "Unknown".to_string());
+
+        tests.push(TestCaseEntry {
+            name,
+            suite,
+            tags,
+            status,
+            xml_path: xml_path_str.clone(),
+            log_html_path: log_html_path.clone(),
+            device_udid: device_udid.clone(),
+            android_version: android_version.clone(),
+            log_offset: whole.start() as u64,
+        });
+    }
+
+    let (size, mtime_secs, mtime_nanos) = file_signature(xml_path).unwrap_or((0, 0, 0));
+    let digest = content_digest(content.as_bytes());
+
+    Some(RunCatalog { xml_path: xml_path_str, size, mtime_secs, mtime_nanos, digest, tests })
+}
+
 #[command]
 pub fn open_log_folder(path: String) -> Result<(), String> {
     println!("Opening folder: {}", path);
@@ -1,167 +1,183 @@
-use std::process::{Command, Stdio};
+use std::process::{Child, Command, Stdio};
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
-use std::sync::Mutex;
+use std::io::{BufRead, BufReader};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
 use tauri::{command, State};
 
 // Constants
 #[cfg(target_os = "windows")]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
-// Wrapper for Tauri State management
-pub struct NgrokState(pub Mutex<Option<u32>>);
+// The agent exposes a local REST API on this address once it is up.
+const AGENT_API: &str = "http://127.0.0.1:4040/api/tunnels";
+
+// How long we wait for a tunnel to show up in the agent API before giving up.
+const START_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Wrapper for Tauri State management. We keep the Child so we own the process tree and
+// can tear it down deterministically, rather than hunting for it by pid.
+pub struct NgrokState(pub Mutex<Option<Child>>);
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct Tunnel {
+    pub public_url: String,
+    pub proto: String,
+    #[serde(default)]
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+struct TunnelsResponse {
+    tunnels: Vec<Tunnel>,
+}
+
+// Does this tunnel's proto satisfy the requested one? `ngrok http` publishes both an
+// "http" and an "https" tunnel, so an http request is happy with either.
+fn proto_matches(requested: &str, tunnel_proto: &str) -> bool {
+    match requested {
+        "http" => tunnel_proto == "http" || tunnel_proto == "https",
+        other => tunnel_proto == other,
+    }
+}
+
+// Read the agent API over its local HTTP endpoint. The repo shells out to CLI tools
+// everywhere else, so we keep that idiom and let curl do the request.
+fn fetch_tunnels() -> Result<Vec<Tunnel>, String> {
+    let mut cmd = Command::new("curl");
+    cmd.args(&["-s", "--max-time", "2", AGENT_API]);
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+
+    let output = cmd.output().map_err(|e| format!("Failed to query ngrok agent API: {}", e))?;
+    if !output.status.success() {
+        return Err("ngrok agent API not reachable".to_string());
+    }
+    let body = String::from_utf8_lossy(&output.stdout);
+    let parsed: TunnelsResponse =
+        serde_json::from_str(&body).map_err(|e| format!("Malformed agent API response: {}", e))?;
+    Ok(parsed.tunnels)
+}
 
 #[command]
 pub async fn start_ngrok(
     state: State<'_, NgrokState>,
-    port: u16, 
-    token: Option<String>
+    protocol: String,
+    port: u16,
+    token: Option<String>,
 ) -> Result<String, String> {
+    let protocol = match protocol.as_str() {
+        "http" | "tcp" => protocol,
+        other => return Err(format!("Unsupported ngrok protocol: {}", other)),
+    };
+
     // 1. Configure Auth Token if provided
     if let Some(auth_token) = &token {
         if !auth_token.is_empty() {
-             let mut cmd = Command::new("ngrok");
-             cmd.args(&["config", "add-authtoken", auth_token]);
-             #[cfg(target_os = "windows")]
-             cmd.creation_flags(CREATE_NO_WINDOW);
-             let _ = cmd.output().map_err(|e| format!("Failed to set authtoken: {}", e))?;
+            let mut cmd = Command::new("ngrok");
+            cmd.args(&["config", "add-authtoken", auth_token]);
+            #[cfg(target_os = "windows")]
+            cmd.creation_flags(CREATE_NO_WINDOW);
+            let _ = cmd.output().map_err(|e| format!("Failed to set authtoken: {}", e))?;
         }
     }
 
-    // 2. Stop existing if any (using the state)
+    // 2. Stop any running tunnel we own before starting a new one.
     {
         let mut lock = state.0.lock().map_err(|_| "Failed to lock mutex")?;
-        if let Some(pid) = *lock {
-             #[cfg(target_os = "windows")]
-             {
-                let mut cmd = Command::new("taskkill");
-                cmd.args(&["/F", "/PID", &pid.to_string()]);
-                cmd.creation_flags(CREATE_NO_WINDOW);
-                let _ = cmd.output();
-             }
-             #[cfg(not(target_os = "windows"))]
-             {
-                let _ = Command::new("kill")
-                    .arg(pid.to_string())
-                    .output();
-             }
-             *lock = None;
+        if let Some(mut child) = lock.take() {
+            let _ = child.kill();
         }
     }
 
-    // 3. Start ngrok tcp <port>
+    // 3. Start `ngrok <protocol> <port>`.
     let mut child_cmd = Command::new("ngrok");
-    child_cmd.args(&["tcp", &port.to_string(), "--log=stdout"])
+    child_cmd
+        .args(&[&protocol, &port.to_string(), "--log=stdout"])
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped());
-    
+        .stderr(Stdio::null());
     #[cfg(target_os = "windows")]
     child_cmd.creation_flags(CREATE_NO_WINDOW);
-    
-    let mut child = child_cmd.spawn()
-        .map_err(|e| format!("Failed to start ngrok: {}", e))?;
 
-    let child_id = child.id();
+    let mut child = child_cmd.spawn().map_err(|e| format!("Failed to start ngrok: {}", e))?;
+
+    // Drain stdout into a ring buffer purely for diagnostics: the URL comes from the
+    // agent API, but the log is the only clue we have when the agent never comes up.
+    let diag: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+    if let Some(stdout) = child.stdout.take() {
+        let diag = diag.clone();
+        thread::spawn(move || {
+            let reader = BufReader::new(stdout);
+            for line in reader.lines().map_while(Result::ok) {
+                if let Ok(mut buf) = diag.lock() {
+                    buf.push(line);
+                    if buf.len() > 20 {
+                        buf.remove(0);
+                    }
+                }
+            }
+        });
+    }
+
     {
         let mut lock = state.0.lock().map_err(|_| "Failed to lock mutex")?;
-        *lock = Some(child_id);
+        *lock = Some(child);
     }
 
-    // 4. Parse output for URL
-    let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
-    let reader = std::io::BufReader::new(stdout);
-    use std::io::BufRead;
-
-    let mut output_buffer = Vec::new();
-    let start = std::time::Instant::now();
-    
-    for line in reader.lines() {
-        if start.elapsed().as_secs() > 10 {
-            let _ = child.kill();
-             let debug_log = output_buffer.join("\n");
-            return Err(format!("Timed out waiting for ngrok URL. Output:\n{}", debug_log));
-        }
-
-        if let Ok(l) = line {
-            output_buffer.push(l.clone());
-            // Keep buffer size reasonable
-            if output_buffer.len() > 20 {
-                output_buffer.remove(0);
+    // 4. Poll the agent API until a tunnel for the requested proto appears.
+    let start = Instant::now();
+    while start.elapsed() < START_TIMEOUT {
+        if let Ok(tunnels) = fetch_tunnels() {
+            if let Some(tunnel) = tunnels.iter().find(|t| proto_matches(&protocol, &t.proto)) {
+                return Ok(tunnel.public_url.clone());
             }
+        }
+        thread::sleep(Duration::from_millis(250));
+    }
 
-            if let Some(idx) = l.find("url=") {
-                let url = l[idx+4..].split_whitespace().next().unwrap_or("").to_string();
-                if !url.is_empty() {
-                     return Ok(url);
-                }
-            }
+    // Timed out: tear the process down and surface whatever it logged.
+    {
+        let mut lock = state.0.lock().map_err(|_| "Failed to lock mutex")?;
+        if let Some(mut child) = lock.take() {
+            let _ = child.kill();
         }
     }
+    let debug_log = diag.lock().map(|b| b.join("\n")).unwrap_or_default();
+    Err(format!("Timed out waiting for ngrok tunnel. Output:\n{}", debug_log))
+}
 
-    let debug_log = output_buffer.join("\n");
-    Err(format!("Ngrok process finished without URL. Output:\n{}", debug_log))
+#[command]
+pub async fn get_ngrok_tunnels() -> Result<Vec<Tunnel>, String> {
+    fetch_tunnels()
 }
 
 #[command]
 pub async fn stop_ngrok(state: State<'_, NgrokState>) -> Result<(), String> {
-    let mut lock = state.0.lock().map_err(|_| "Failed to lock mutex")?;
-    
-    if let Some(pid) = *lock {
-        #[cfg(target_os = "windows")]
-        {
-            let _ = Command::new("taskkill")
-                .args(&["/F", "/PID", &pid.to_string()])
-                .creation_flags(CREATE_NO_WINDOW)
-                .output();
-        }
-        #[cfg(not(target_os = "windows"))]
-        {
-             let _ = Command::new("kill")
-                .arg(pid.to_string())
-                .output();
-        }
-        *lock = None;
-    }
-    
-    // Safety net
-    #[cfg(target_os = "windows")]
-    {
-        let _ = Command::new("taskkill")
-            .args(&["/F", "/IM", "ngrok.exe"])
-            .creation_flags(CREATE_NO_WINDOW)
-            .output();
-    }
-    #[cfg(not(target_os = "windows"))]
     {
-        let _ = Command::new("pkill")
-            .arg("ngrok")
-            .output();
+        let mut lock = state.0.lock().map_err(|_| "Failed to lock mutex")?;
+        if let Some(mut child) = lock.take() {
+            let _ = child.kill();
+        }
     }
-        
+
+    // Safety net: kill any stray agent the state lost track of.
+    kill_stray_agents();
     Ok(())
 }
 
 pub fn shutdown_ngrok(state: &State<'_, NgrokState>) {
-    if let Ok(lock) = state.0.lock() {
-        if let Some(pid) = *lock {
-            #[cfg(target_os = "windows")]
-            {
-                let _ = Command::new("taskkill")
-                    .args(&["/F", "/PID", &pid.to_string()])
-                    .creation_flags(CREATE_NO_WINDOW)
-                    .output();
-            }
-            #[cfg(not(target_os = "windows"))]
-            {
-                let _ = Command::new("kill")
-                    .arg(pid.to_string())
-                    .output();
-            }
-            // *lock = None; // Not strictly necessary on exit, but good practice
+    if let Ok(mut lock) = state.0.lock() {
+        if let Some(mut child) = lock.take() {
+            let _ = child.kill();
         }
     }
+    kill_stray_agents();
+}
 
-    // Safety net: Kill by name
+fn kill_stray_agents() {
     #[cfg(target_os = "windows")]
     {
         let _ = Command::new("taskkill")
@@ -171,8 +187,6 @@ pub fn shutdown_ngrok(state: &State<'_, NgrokState>) {
     }
     #[cfg(not(target_os = "windows"))]
     {
-        let _ = Command::new("pkill")
-            .arg("ngrok")
-            .output();
+        let _ = Command::new("pkill").arg("ngrok").output();
     }
 }
@@ -1,59 +1,400 @@
 use tauri::{AppHandle, Emitter, State, Manager};
-use std::process::{Command, Stdio, Child};
+use std::process::{Command, Stdio};
 use std::io::{BufRead, BufReader};
 use std::thread;
-use std::sync::Mutex;
+use shared_child::SharedChild;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use std::collections::HashMap;
 use chrono;
 
-pub struct TestState(pub Mutex<HashMap<String, Child>>);
+/// Per-run control handle. We keep the shared child so the stop path can signal and
+/// kill it, plus two flags the wait thread reads to tell a user-cancelled run from a
+/// natural exit and a graceful stop from an escalated kill.
+pub struct RunHandle {
+    // Shared handle so the wait thread can block on `wait()` while the stop path kills
+    // it concurrently from its own clone of the Arc.
+    child: Arc<SharedChild>,
+    // Set when the run is user-cancelled, so the waiter reports a stop rather than a
+    // natural finish. `killed` is set when the graceful interrupt timed out and we had
+    // to hard-kill, so the UI knows whether reports were flushed.
+    stopped: Arc<AtomicBool>,
+    killed: Arc<AtomicBool>,
+}
+
+pub struct TestState(pub Mutex<HashMap<String, RunHandle>>);
+
+// Default grace period we give `robot` to finalize output.xml/log.html after a
+// graceful interrupt before we escalate to an outright kill.
+const DEFAULT_STOP_TIMEOUT: u64 = 5;
+
+/// Ask the process to stop the way a Ctrl-C at the console would, so Robot Framework
+/// runs its shutdown hook and writes out usable reports. Mirrors std's
+/// `PleaseExitSignal`/`MustDieSignal` split: this is the "please exit" half, and the
+/// caller escalates to a hard kill if the process ignores it.
+fn interrupt_pid(pid: u32) {
+    #[cfg(not(target_os = "windows"))]
+    {
+        use nix::sys::signal::{self, Signal};
+        use nix::unistd::Pid;
+        let _ = signal::kill(Pid::from_raw(pid as i32), Signal::SIGINT);
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        // There is no SIGINT on Windows. Because `robot` is spawned into its own
+        // process group (CREATE_NEW_PROCESS_GROUP), we can raise CTRL_BREAK for that
+        // group and the Python runtime treats it like an interrupt.
+        const CTRL_BREAK_EVENT: u32 = 1;
+        extern "system" {
+            fn GenerateConsoleCtrlEvent(dwCtrlEvent: u32, dwProcessGroupId: u32) -> i32;
+        }
+        unsafe {
+            GenerateConsoleCtrlEvent(CTRL_BREAK_EVENT, pid);
+        }
+    }
+}
+
+/// The "must die" half of the stop: force-kill by pid (the whole process tree via
+/// `taskkill /T` on Windows, the process itself via SIGKILL elsewhere).
+fn kill_pid(pid: u32) {
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        let _ = std::process::Command::new("taskkill")
+            .args(&["/F", "/T", "/PID", &pid.to_string()])
+            .creation_flags(0x08000000) // CREATE_NO_WINDOW
+            .output();
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        use nix::sys::signal::{self, Signal};
+        use nix::unistd::Pid;
+        let _ = signal::kill(Pid::from_raw(pid as i32), Signal::SIGKILL);
+    }
+}
 
 #[tauri::command]
-pub fn stop_robot_test(state: State<'_, TestState>, run_id: String) -> Result<String, String> {
-    let mut procs = state.0.lock().map_err(|e| e.to_string())?;
-    
-    if let Some(mut child) = procs.remove(&run_id) {
-         // Handle Windows Process Tree Killing
-        #[cfg(target_os = "windows")]
-        {
-            use std::os::windows::process::CommandExt;
-            let pid = child.id();
-            let _ = Command::new("taskkill")
-                .args(&["/F", "/T", "/PID", &pid.to_string()])
-                .creation_flags(0x08000000) // CREATE_NO_WINDOW
-                .output();
-                
-            let _ = child.kill();
+pub fn stop_robot_test(app: AppHandle, state: State<'_, TestState>, run_id: String, stop_timeout: Option<u64>) -> Result<String, String> {
+    let (child, stopped, killed) = {
+        let procs = state.0.lock().map_err(|e| e.to_string())?;
+        match procs.get(&run_id) {
+            Some(h) => (h.child.clone(), h.stopped.clone(), h.killed.clone()),
+            None => return Err(format!("Test {} not running", run_id)),
         }
+    };
+    let pid = child.id();
 
-        #[cfg(not(target_os = "windows"))]
-        {
-             let _ = child.kill();
+    // Flag the run as user-cancelled so the waiter emits a stop rather than a natural
+    // finish once the process winds down.
+    stopped.store(true, Ordering::SeqCst);
+
+    // Stage 1: ask nicely so the reports get flushed.
+    interrupt_pid(pid);
+
+    // Stage 2: if it hasn't exited within the (caller-supplied) grace period, escalate
+    // to a hard kill. We keep our own clone of the Arc and kill concurrently with
+    // the parked wait thread. The state map is the liveness check — the wait thread
+    // removes the run the instant the process exits — so a run that stopped gracefully
+    // in time is never killed and is reported as graceful.
+    let timeout = Duration::from_secs(stop_timeout.unwrap_or(DEFAULT_STOP_TIMEOUT));
+    let escalate_app = app.clone();
+    let escalate_id = run_id.clone();
+    thread::spawn(move || {
+        thread::sleep(timeout);
+        let still_running = escalate_app
+            .state::<TestState>()
+            .0
+            .lock()
+            .map(|m| m.contains_key(&escalate_id))
+            .unwrap_or(false);
+        if still_running {
+            killed.store(true, Ordering::SeqCst);
+            kill_pid(pid);
+            let _ = child.kill();
         }
+    });
 
-        let _ = child.wait();
-        return Ok(format!("Test {} stopped", run_id));
-    }
-    Err(format!("Test {} not running", run_id))
+    Ok(format!("Test {} stopping", run_id))
 }
 
 #[derive(serde::Serialize, Clone)]
-struct TestOutput {
+struct TestFinished {
     run_id: String,
-    message: String,
+    // None when the process was killed by a signal rather than exiting normally.
+    exit_code: Option<i32>,
+    // Number of failed critical tests, when the exit code encodes one (1..=250).
+    failed_count: Option<u32>,
+    success: bool,
+    // Terminating signal on Unix when exit_code is None.
+    signal: Option<i32>,
+    // Human-readable summary the frontend renders as a pass/fail badge.
+    category: String,
+    // Authoritative counts parsed from output.xml once the run is done. None when the
+    // file is missing or unreadable (e.g. the run was killed before it was written).
+    stats: Option<SuiteStats>,
 }
 
 #[derive(serde::Serialize, Clone)]
-struct TestFinished {
+struct SuiteStats {
+    total: u32,
+    passed: u32,
+    failed: u32,
+    skipped: u32,
+}
+
+#[derive(serde::Serialize, Clone)]
+struct TestCaseResult {
     run_id: String,
+    name: String,
+    // "PASS" | "FAIL" | "SKIP", straight from the console line.
     status: String,
+    // Failure detail gathered from the non-empty lines that follow a FAIL, empty otherwise.
+    message: String,
+}
+
+#[derive(serde::Serialize, Clone)]
+struct SuiteSummary {
+    run_id: String,
+    total: u32,
+    passed: u32,
+    failed: u32,
+    skipped: u32,
+}
+
+// Where `ConsoleParser` is in the middle of a suite header/footer banner, i.e. a
+// `====` rule, the suite name, then another `====` rule. Only `=` rules bound banners;
+// `-` rules just separate a failed test's message from what follows.
+enum BannerState {
+    None,
+    // Just saw an opening `=` rule; the next non-blank line may be a suite name.
+    AfterSeparator,
+    // Saw an `=` rule then a name; confirmed as a suite banner if another `=` rule
+    // follows immediately.
+    PendingName(String),
+}
+
+/// Streaming parser over Robot Framework's `--console verbose` output. It forwards every
+/// raw line unchanged (so the plain log view keeps working) and, on top of that,
+/// recognizes the console grammar to emit structured `test-case-result` /
+/// `suite-summary` events the UI can render as a live tree. A FAIL line is held back
+/// until the detail lines that follow it have been collected into a message.
+struct ConsoleParser {
+    app: AppHandle,
+    run_id: String,
+    raw_event: String,
+    re_result: regex::Regex,
+    re_stats: regex::Regex,
+    re_separator: regex::Regex,
+    re_banner_separator: regex::Regex,
+    // Name of the failing test whose message we're still gathering, if any.
+    pending: Option<String>,
+    message: String,
+    banner_state: BannerState,
+    // Suite names seen in a confirmed header/footer banner, so their `Name | PASS |`
+    // summary row (Robot prints the same shape for suites as for tests) isn't
+    // mistaken for a test-case result.
+    known_suites: std::collections::HashSet<String>,
+}
+
+impl ConsoleParser {
+    fn new(app: AppHandle, run_id: String) -> Self {
+        let raw_event = format!("test-output-{}", run_id);
+        ConsoleParser {
+            app,
+            run_id,
+            raw_event,
+            // "Test Name <padding> | PASS |" — trailing status cell.
+            re_result: regex::Regex::new(r"^(.*\S)\s+\|\s+(PASS|FAIL|SKIP)\s+\|$").unwrap(),
+            // Final statistics line, e.g. "3 tests, 2 passed, 1 failed, 0 skipped".
+            re_stats: regex::Regex::new(
+                r"^(\d+)\s+tests?,\s+(\d+)\s+passed,\s+(\d+)\s+failed(?:,\s+(\d+)\s+skipped)?",
+            )
+            .unwrap(),
+            // Robot's horizontal rules, e.g. a run of '-' or '='.
+            re_separator: regex::Regex::new(r"^[-=]{10,}$").unwrap(),
+            // Suite header/footer banners specifically use '=', never '-'.
+            re_banner_separator: regex::Regex::new(r"^={10,}$").unwrap(),
+            pending: None,
+            message: String::new(),
+            banner_state: BannerState::None,
+            known_suites: std::collections::HashSet::new(),
+        }
+    }
+
+    fn feed(&mut self, line: &str) {
+        // Raw stream first, so nothing structured is ever lost to the plain view.
+        let _ = self.app.emit(&self.raw_event, line.to_string());
+
+        let trimmed = line.trim_end();
+
+        if self.re_separator.is_match(trimmed) {
+            self.flush_pending();
+            if self.re_banner_separator.is_match(trimmed) {
+                if let BannerState::PendingName(name) = &self.banner_state {
+                    self.known_suites.insert(name.clone());
+                    self.banner_state = BannerState::None;
+                } else {
+                    self.banner_state = BannerState::AfterSeparator;
+                }
+            } else {
+                self.banner_state = BannerState::None;
+            }
+            return;
+        }
+
+        if let Some(caps) = self.re_stats.captures(trimmed) {
+            self.banner_state = BannerState::None;
+            self.flush_pending();
+            let total = caps[1].parse().unwrap_or(0);
+            let passed = caps[2].parse().unwrap_or(0);
+            let failed = caps[3].parse().unwrap_or(0);
+            let skipped = caps.get(4).and_then(|m| m.as_str().parse().ok()).unwrap_or(0);
+            let _ = self.app.emit(
+                &format!("suite-summary-{}", self.run_id),
+                SuiteSummary { run_id: self.run_id.clone(), total, passed, failed, skipped },
+            );
+            return;
+        }
+
+        if let Some(caps) = self.re_result.captures(trimmed) {
+            self.banner_state = BannerState::None;
+            self.flush_pending();
+            let name = caps[1].trim().to_string();
+            let status = caps[2].to_string();
+            if self.known_suites.contains(&name) {
+                // This is a suite's own summary row, not a test case; the numeric
+                // stats line that follows carries its real totals.
+                return;
+            }
+            if status == "FAIL" {
+                // Hold it back; the failure message lands on the following lines.
+                self.pending = Some(name);
+                self.message.clear();
+            } else {
+                self.emit_result(name, status, String::new());
+            }
+            return;
+        }
+
+        if matches!(self.banner_state, BannerState::AfterSeparator) {
+            let candidate = trimmed.trim();
+            if !candidate.is_empty() {
+                self.banner_state = BannerState::PendingName(candidate.to_string());
+            }
+            return;
+        }
+
+        // Any other non-empty line while a FAIL is pending is part of its message.
+        if self.pending.is_some() {
+            let detail = line.trim();
+            if !detail.is_empty() {
+                if !self.message.is_empty() {
+                    self.message.push('\n');
+                }
+                self.message.push_str(detail);
+            }
+        }
+    }
+
+    fn flush_pending(&mut self) {
+        if let Some(name) = self.pending.take() {
+            let message = std::mem::take(&mut self.message);
+            self.emit_result(name, "FAIL".to_string(), message);
+        }
+    }
+
+    fn emit_result(&self, name: String, status: String, message: String) {
+        let _ = self.app.emit(
+            &format!("test-case-result-{}", self.run_id),
+            TestCaseResult { run_id: self.run_id.clone(), name, status, message },
+        );
+    }
+}
+
+/// Parse the authoritative pass/fail/skip counts from the `All Tests` aggregate in a
+/// finished run's output.xml. Mirrors the stat-parsing regex used by the history view.
+fn parse_output_xml_stats(output_dir: &str) -> Option<SuiteStats> {
+    let xml_path = std::path::Path::new(output_dir).join("output.xml");
+    let content = std::fs::read_to_string(xml_path).ok()?;
+    let re = regex::Regex::new(
+        r#"<stat pass="(\d+)" fail="(\d+)"(?: skip="(\d+)")?[^>]*>All Tests</stat>"#,
+    )
+    .ok()?;
+    let caps = re.captures(&content)?;
+    let passed: u32 = caps[1].parse().ok()?;
+    let failed: u32 = caps[2].parse().ok()?;
+    let skipped: u32 = caps.get(3).and_then(|m| m.as_str().parse().ok()).unwrap_or(0);
+    Some(SuiteStats { total: passed + failed + skipped, passed, failed, skipped })
+}
+
+/// Turn a finished `robot` process status into a structured summary. Robot Framework
+/// encodes the number of failed critical tests in its exit code: 0 is a clean pass,
+/// 1..=250 is the failure count, and 251/252/253 are reserved error codes.
+fn classify_finish(run_id: String, status: std::process::ExitStatus) -> TestFinished {
+    match status.code() {
+        Some(code) => {
+            let (failed_count, success, category) = match code {
+                0 => (Some(0), true, "All tests passed".to_string()),
+                1..=250 => (Some(code as u32), false, format!("{} test(s) failed", code)),
+                251 => (None, false, "Help or version information requested".to_string()),
+                252 => (None, false, "Invalid command-line options".to_string()),
+                253 => (None, false, "Internal framework error".to_string()),
+                _ => (None, false, format!("Unexpected exit code {}", code)),
+            };
+            TestFinished { run_id, exit_code: Some(code), failed_count, success, signal: None, category, stats: None }
+        }
+        None => {
+            // No exit code means the process was terminated by a signal (Unix).
+            #[cfg(not(target_os = "windows"))]
+            let signal = {
+                use std::os::unix::process::ExitStatusExt;
+                status.signal()
+            };
+            #[cfg(target_os = "windows")]
+            let signal: Option<i32> = None;
+
+            let category = match signal {
+                Some(s) => format!("Terminated by signal {}", s),
+                None => "Terminated abnormally".to_string(),
+            };
+            TestFinished { run_id, exit_code: None, failed_count: None, success: false, signal, category, stats: None }
+        }
+    }
+}
+
+/// Everything needed to launch one `robot` run. Kept as a struct so watch mode can
+/// stash a run's parameters and relaunch it on file changes.
+#[derive(Clone)]
+pub struct RobotRunConfig {
+    pub run_id: String,
+    pub test_path: Option<String>,
+    pub output_dir: String,
+    pub device: Option<String>,
+    pub arguments_file: Option<String>,
+    pub timestamp_outputs: Option<bool>,
+    pub device_model: Option<String>,
+    pub android_version: Option<String>,
+    pub working_dir: Option<String>,
 }
 
 #[tauri::command]
 pub fn run_robot_test(app: AppHandle, state: State<'_, TestState>, run_id: String, test_path: Option<String>, output_dir: String, device: Option<String>, arguments_file: Option<String>, timestamp_outputs: Option<bool>, device_model: Option<String>, android_version: Option<String>, working_dir: Option<String>) -> Result<String, String> {
+    start_robot_run(&app, &state, RobotRunConfig {
+        run_id, test_path, output_dir, device, arguments_file, timestamp_outputs, device_model, android_version, working_dir,
+    })
+}
+
+pub fn start_robot_run(app: &AppHandle, state: &TestState, cfg: RobotRunConfig) -> Result<String, String> {
+    let RobotRunConfig { run_id, test_path, output_dir, device, arguments_file, timestamp_outputs, device_model, android_version, working_dir } = cfg;
+    // Derive a per-run output directory from the run id so concurrent runs against
+    // different devices don't clobber each other's output.xml / log.html.
+    let run_output_dir = std::path::Path::new(&output_dir).join(&run_id);
+    let _ = std::fs::create_dir_all(&run_output_dir);
+
     // Resolve absolute path for output_dir to ensure clean logs
-    let abs_output_dir = std::fs::canonicalize(&output_dir)
+    let abs_output_dir = std::fs::canonicalize(&run_output_dir)
         .map(|p| {
             let s = p.to_string_lossy().to_string();
             // Remove Windows UNC prefix if present
@@ -63,7 +404,7 @@ pub fn run_robot_test(app: AppHandle, state: State<'_, TestState>, run_id: Strin
                 s
             }
         })
-        .unwrap_or_else(|_| output_dir.clone());
+        .unwrap_or_else(|_| run_output_dir.to_string_lossy().to_string());
 
     let mut args = vec!["-d", &abs_output_dir, "--console", "verbose"];
 
@@ -130,110 +471,320 @@ pub fn run_robot_test(app: AppHandle, state: State<'_, TestState>, run_id: Strin
     #[cfg(target_os = "windows")]
     {
         use std::os::windows::process::CommandExt;
-        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+        // CREATE_NO_WINDOW | CREATE_NEW_PROCESS_GROUP so the stop path can target the
+        // child's group with a CTRL_BREAK event (see interrupt_pid).
+        cmd.creation_flags(0x08000000 | 0x00000200);
     }
 
-    let mut child = cmd
-        .env("PYTHONIOENCODING", "utf-8")
+    cmd.env("PYTHONIOENCODING", "utf-8")
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
+        .stderr(Stdio::piped());
+
+    // SharedChild gives us a handle that can be waited on and killed from different
+    // threads, so the wait thread blocks once while the stop path kills concurrently.
+    let shared = SharedChild::spawn(&mut cmd)
         .map_err(|e| format!("Failed to start robot: {}. Make sure 'robot' is requested in PATH.", e))?;
+    let child = Arc::new(shared);
 
-    let stdout = child.stdout.take().ok_or("Failed to open stdout")?;
-    let stderr = child.stderr.take().ok_or("Failed to open stderr")?;
+    let stdout = child.take_stdout().ok_or("Failed to open stdout")?;
+    let stderr = child.take_stderr().ok_or("Failed to open stderr")?;
 
-    // Streaming threads
-    let app_handle = app.clone();
-    let rid = run_id.clone();
+    // Register the run before we start waiting so stop_robot_test can find it.
+    let stopped = Arc::new(AtomicBool::new(false));
+    let killed = Arc::new(AtomicBool::new(false));
+    {
+        let mut procs = state.0.lock().map_err(|e| e.to_string())?;
+        if procs.contains_key(&run_id) {
+             return Err(format!("Run ID {} already exists", run_id));
+        }
+        procs.insert(run_id.clone(), RunHandle { child: child.clone(), stopped: stopped.clone(), killed: killed.clone() });
+    }
+
+    // Stream stdout through the console parser: it forwards raw lines and emits the
+    // structured test-case-result / suite-summary events on top.
+    let mut parser = ConsoleParser::new(app.clone(), run_id.clone());
     thread::spawn(move || {
-        let mut reader = BufReader::new(stdout);
-        let mut buf = Vec::new();
-        while let Ok(n) = reader.read_until(b'\n', &mut buf) {
-            if n == 0 { break; }
-            let line = String::from_utf8_lossy(&buf).to_string();
-            let _ = app_handle.emit("test-output", TestOutput { 
-                run_id: rid.clone(), 
-                message: line.trim_end().to_string() 
-            });
-            buf.clear();
+        let reader = BufReader::new(stdout);
+        for line in reader.lines().map_while(Result::ok) {
+            parser.feed(&line);
         }
+        parser.flush_pending();
     });
 
-    let app_handle_err = app.clone();
-    let rid_err = run_id.clone();
+    let app_err = app.clone();
+    let err_event = format!("test-output-{}", run_id);
     thread::spawn(move || {
-        let mut reader = BufReader::new(stderr);
-        let mut buf = Vec::new();
-        while let Ok(n) = reader.read_until(b'\n', &mut buf) {
-            if n == 0 { break; }
-            let line = String::from_utf8_lossy(&buf).to_string();
-            let _ = app_handle_err.emit("test-output", TestOutput { 
-                run_id: rid_err.clone(), 
-                message: format!("STDERR: {}", line.trim_end()) 
-            });
-            buf.clear();
+        let reader = BufReader::new(stderr);
+        for line in reader.lines().map_while(Result::ok) {
+            let _ = app_err.emit(&err_event, format!("STDERR: {}", line));
         }
     });
 
-    // Store child in state
+    // One thread blocks on wait() and emits the instant the process exits — no polling
+    // loop, no 500ms tail, no global-mutex timer.
+    let app_finish = app.clone();
+    let rid = run_id.clone();
+    let finish_dir = abs_output_dir.clone();
+    thread::spawn(move || {
+        let status = child.wait();
+
+        // Drop our control handle now that the process is gone.
+        let state = app_finish.state::<TestState>();
+        if let Ok(mut procs) = state.0.lock() {
+            procs.remove(&rid);
+        }
+
+        let mut payload = match status {
+            Ok(status) => classify_finish(rid.clone(), status),
+            Err(e) => TestFinished {
+                run_id: rid.clone(),
+                exit_code: None,
+                failed_count: None,
+                success: false,
+                signal: None,
+                category: format!("Error checking status: {}", e),
+                stats: None,
+            },
+        };
+
+        // Prefer output.xml's counts as the authoritative result when it exists.
+        payload.stats = parse_output_xml_stats(&finish_dir);
+
+        if stopped.load(Ordering::SeqCst) {
+            // Graceful if the interrupt alone ended the run (reports were flushed);
+            // killed if we had to escalate past the stop timeout.
+            if killed.load(Ordering::SeqCst) {
+                let _ = app_finish.emit(&format!("test-killed-{}", rid), payload);
+            } else {
+                let _ = app_finish.emit(&format!("test-stopped-graceful-{}", rid), payload);
+            }
+        } else {
+            let _ = app_finish.emit(&format!("test-finished-{}", rid), payload);
+        }
+    });
+
+    Ok("Started".to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Watch mode
+// ---------------------------------------------------------------------------
+
+// How long to coalesce a burst of file-change events into a single rerun.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// What to do when files change while a run is already in flight, mirroring
+/// watchexec's on-busy-update options.
+#[derive(Clone, Copy, PartialEq)]
+enum WatchPolicy {
+    // Let the current run finish, then rerun once.
+    Queue,
+    // Gracefully stop the in-flight run and start a fresh one.
+    Restart,
+    // Ignore changes while a run is in progress.
+    DoNothing,
+}
+
+impl WatchPolicy {
+    fn parse(s: Option<String>) -> Self {
+        match s.as_deref() {
+            Some("restart") => WatchPolicy::Restart,
+            Some("do-nothing") => WatchPolicy::DoNothing,
+            _ => WatchPolicy::Queue,
+        }
+    }
+}
+
+pub struct WatchSession {
+    stop: Arc<AtomicBool>,
+    // The watcher must be kept alive for events to keep flowing.
+    _watcher: notify::RecommendedWatcher,
+}
+
+pub struct WatchState(pub Mutex<HashMap<String, WatchSession>>);
+
+#[derive(serde::Serialize, Clone)]
+struct WatchTriggered {
+    run_id: String,
+    paths: Vec<String>,
+}
+
+// Only a change to one of Robot Framework's source file types should trigger a rerun.
+fn is_watchable(path: &std::path::Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("robot") | Some("resource") | Some("py")
+    )
+}
+
+#[tauri::command]
+pub fn watch_robot_test(
+    app: AppHandle,
+    watch_state: State<'_, WatchState>,
+    run_id: String,
+    test_path: Option<String>,
+    output_dir: String,
+    device: Option<String>,
+    arguments_file: Option<String>,
+    timestamp_outputs: Option<bool>,
+    device_model: Option<String>,
+    android_version: Option<String>,
+    working_dir: Option<String>,
+    policy: Option<String>,
+) -> Result<String, String> {
+    // Tear down any existing watcher for this run id.
     {
-        let mut procs = state.0.lock().map_err(|e| e.to_string())?;
-        if procs.contains_key(&run_id) {
-             return Err(format!("Run ID {} already exists", run_id));
+        let mut sessions = watch_state.0.lock().map_err(|e| e.to_string())?;
+        if let Some(session) = sessions.remove(&run_id) {
+            session.stop.store(true, Ordering::SeqCst);
         }
-        procs.insert(run_id.clone(), child);
     }
 
-    // Monitoring thread
-    let app_handle_finish = app.clone();
-    let rid_monitor = run_id.clone();
-    
-    thread::spawn(move || {
-        loop {
-            thread::sleep(Duration::from_millis(500));
-            
-            let state = app_handle_finish.state::<TestState>();
-            let mut procs: std::sync::MutexGuard<HashMap<String, Child>> = match state.0.lock() {
-                Ok(g) => g,
-                Err(_) => break, 
-            };
+    let cfg = RobotRunConfig {
+        run_id: run_id.clone(),
+        test_path: test_path.clone(),
+        output_dir,
+        device,
+        arguments_file,
+        timestamp_outputs,
+        device_model,
+        android_version,
+        working_dir: working_dir.clone(),
+    };
+    let policy = WatchPolicy::parse(policy);
 
-            // Check if process exists and is running
-            let mut finished = false;
-            let mut status_msg = String::new();
-
-            if let Some(child) = procs.get_mut(&rid_monitor) {
-                match child.try_wait() {
-                    Ok(Some(status)) => {
-                        finished = true;
-                        status_msg = format!("Exit Code: {}", status);
-                    },
-                    Ok(None) => {}, // Still running
-                    Err(e) => {
-                        finished = true;
-                        status_msg = format!("Error checking status: {}", e);
-                    }
-                }
-            } else {
-                // Removed from map (stopped externally)
+    // Watch the working dir when given, otherwise the test path itself.
+    let watch_root = working_dir
+        .filter(|w| !w.is_empty())
+        .or_else(|| test_path.clone())
+        .ok_or("Nothing to watch: provide a test_path or working_dir")?;
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |res| {
+        let _ = tx.send(res);
+    })
+    .map_err(|e| format!("Failed to create watcher: {}", e))?;
+
+    use notify::Watcher;
+    watcher
+        .watch(std::path::Path::new(&watch_root), notify::RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch {}: {}", watch_root, e))?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread_stop = stop.clone();
+    let thread_app = app.clone();
+
+    std::thread::spawn(move || {
+        while let Ok(first) = rx.recv() {
+            if thread_stop.load(Ordering::SeqCst) {
                 break;
             }
 
-            if finished {
-                // Remove from map
-                procs.remove(&rid_monitor);
-                // Drop lock before emitting? No, try_wait is fast.
-                drop(procs); 
+            let mut changed: std::collections::HashSet<std::path::PathBuf> = std::collections::HashSet::new();
+            collect_changed(first, &mut changed);
+
+            // Coalesce the rest of the burst within the debounce window.
+            while let Ok(ev) = rx.recv_timeout(WATCH_DEBOUNCE) {
+                collect_changed(ev, &mut changed);
+            }
+
+            let relevant: Vec<String> = changed
+                .iter()
+                .filter(|p| is_watchable(p))
+                .map(|p| p.to_string_lossy().to_string())
+                .collect();
+
+            if relevant.is_empty() || thread_stop.load(Ordering::SeqCst) {
+                continue;
+            }
+
+            let _ = thread_app.emit(
+                &format!("watch-triggered-{}", cfg.run_id),
+                WatchTriggered { run_id: cfg.run_id.clone(), paths: relevant },
+            );
+
+            let test_state = thread_app.state::<TestState>();
+            let running = is_running(&test_state, &cfg.run_id);
+
+            match policy {
+                WatchPolicy::DoNothing if running => continue,
+                WatchPolicy::Queue => {
+                    // Wait for the in-flight run to finish before rerunning.
+                    while is_running(&test_state, &cfg.run_id) {
+                        if thread_stop.load(Ordering::SeqCst) {
+                            break;
+                        }
+                        std::thread::sleep(Duration::from_millis(200));
+                    }
+                }
+                WatchPolicy::Restart if running => {
+                    graceful_stop_blocking(&test_state, &cfg.run_id);
+                }
+                _ => {}
+            }
 
-                let _ = app_handle_finish.emit("test-finished", TestFinished { 
-                    run_id: rid_monitor, 
-                    status: status_msg 
-                });
+            if thread_stop.load(Ordering::SeqCst) {
                 break;
             }
+            let _ = start_robot_run(&thread_app, &test_state, cfg.clone());
         }
     });
 
-    Ok("Started".to_string())
+    watch_state
+        .0
+        .lock()
+        .map_err(|e| e.to_string())?
+        .insert(run_id.clone(), WatchSession { stop, _watcher: watcher });
+
+    Ok(format!("Watching {}", watch_root))
+}
+
+#[tauri::command]
+pub fn stop_watch(watch_state: State<'_, WatchState>, run_id: String) -> Result<(), String> {
+    if let Some(session) = watch_state.0.lock().map_err(|e| e.to_string())?.remove(&run_id) {
+        session.stop.store(true, Ordering::SeqCst);
+    }
+    Ok(())
+}
+
+fn collect_changed(res: notify::Result<notify::Event>, out: &mut std::collections::HashSet<std::path::PathBuf>) {
+    if let Ok(event) = res {
+        for path in event.paths {
+            out.insert(path);
+        }
+    }
+}
+
+fn is_running(state: &TestState, run_id: &str) -> bool {
+    state.0.lock().map(|m| m.contains_key(run_id)).unwrap_or(false)
+}
+
+// Gracefully stop a run and block until it's gone, escalating to a hard kill if it
+// overstays the default grace period. Used by watch mode's restart policy.
+fn graceful_stop_blocking(state: &TestState, run_id: &str) {
+    let (pid, killed) = match state.0.lock().ok().and_then(|m| m.get(run_id).map(|h| (h.child.id(), h.stopped.clone(), h.killed.clone()))) {
+        Some((pid, stopped, killed)) => {
+            stopped.store(true, Ordering::SeqCst);
+            (pid, killed)
+        }
+        None => return,
+    };
+
+    interrupt_pid(pid);
+
+    let start = std::time::Instant::now();
+    while start.elapsed() < Duration::from_secs(DEFAULT_STOP_TIMEOUT) {
+        if !is_running(state, run_id) {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    }
+
+    if is_running(state, run_id) {
+        killed.store(true, Ordering::SeqCst);
+        kill_pid(pid);
+        while is_running(state, run_id) {
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
 }
@@ -0,0 +1,256 @@
+use tauri::command;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use serde::Serialize;
+use serde_json::Value;
+use regex::Regex;
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+// Every probe below shells out to a third-party tool (adb, appium, a `.cmd` shim on
+// Windows) that can stall indefinitely if a device is mid-boot or the shim deadlocks.
+// Bounding each call keeps one hung tool from freezing the whole version panel.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize)]
+pub struct SystemVersions {
+    pub adb: String,
+    pub appium: String,
+    pub uiautomator2: String,
+    pub scrcpy: String,
+    pub robot: String,
+    pub python: String,
+    pub node: String,
+}
+
+#[command]
+pub fn get_system_versions() -> SystemVersions {
+    let adb_raw = get_version("adb", &["--version"]);
+    let adb = extract_version(&adb_raw, r"Android Debug Bridge version ([\d\.]+)");
+
+    let node = get_version("node", &["--version"]); // Usually just vX.X.X
+
+    let python_raw = get_version("python", &["--version"]);
+    let python = extract_version(&python_raw, r"Python ([\d\.]+)");
+
+    let scrcpy_raw = get_version("scrcpy", &["--version"]);
+    let scrcpy = extract_version(&scrcpy_raw, r"scrcpy ([\d\.]+)");
+
+    // Check Appium and determine command
+    let (appium_raw, appium_cmd) = if let Some(v) = try_get_version("appium", &["--version"]) {
+        (v, "appium")
+    } else if let Some(v) = try_get_version("appium.cmd", &["--version"]) {
+        (v, "appium.cmd")
+    } else {
+        ("Not Found".to_string(), "appium")
+    };
+    let appium = appium_raw; // usually just X.X.X
+
+    // Check Robot - Output often exits with 1, so use loose check
+    let robot_raw = if let Some(v) = try_get_version_loose("robot", &["--version"]) {
+        v
+    } else if let Some(v) = try_get_version_loose("python", &["-m", "robot", "--version"]) {
+        v
+    } else {
+        "Not Found".to_string()
+    };
+    let robot = extract_version(&robot_raw, r"Robot Framework ([\d\.]+)");
+
+    // Check UiAutomator2 using the found Appium command
+    let uiautomator2 = if appium != "Not Found" {
+        check_uiautomator2(appium_cmd)
+    } else {
+        "Not Found".to_string()
+    };
+
+    SystemVersions {
+        adb,
+        appium,
+        uiautomator2,
+        scrcpy,
+        robot,
+        python,
+        node,
+    }
+}
+
+fn extract_version(input: &str, pattern: &str) -> String {
+    if input == "Not Found" {
+        return input.to_string();
+    }
+    if let Ok(re) = Regex::new(pattern) {
+        if let Some(caps) = re.captures(input) {
+            if let Some(m) = caps.get(1) {
+                return m.as_str().to_string();
+            }
+        }
+    }
+    input.to_string()
+}
+
+// Helper to return Option<String> for cleaner logic
+fn try_get_version(cmd: &str, args: &[&str]) -> Option<String> {
+    let res = get_version_internal(cmd, args, true); // strict
+    if res == "Not Found" { None } else { Some(res) }
+}
+
+fn try_get_version_loose(cmd: &str, args: &[&str]) -> Option<String> {
+    let res = get_version_internal(cmd, args, false); // loose (ignore exit code)
+    if res == "Not Found" { None } else { Some(res) }
+}
+
+fn get_version(cmd: &str, args: &[&str]) -> String {
+    get_version_internal(cmd, args, true)
+}
+
+fn get_version_internal(cmd: &str, args: &[&str], strict: bool) -> String {
+    // Try executing directly
+    let mut command = Command::new(cmd);
+    command.args(args);
+
+    #[cfg(target_os = "windows")]
+    command.creation_flags(CREATE_NO_WINDOW);
+
+    match run_with_timeout(command, PROBE_TIMEOUT) {
+        Ok(output) => {
+            if !strict || output.status.success() {
+                if let Some(line) = first_nonempty_line(&output) {
+                    return line;
+                }
+            }
+        }
+        Err(_) => {
+            // Fallback to shell execution on Windows for .cmd/.bat resolution
+            #[cfg(target_os = "windows")]
+            {
+                let mut shell_cmd = Command::new("cmd");
+                shell_cmd.creation_flags(CREATE_NO_WINDOW);
+                shell_cmd.args(&["/C", cmd]);
+                shell_cmd.args(args);
+                if let Ok(output) = run_with_timeout(shell_cmd, PROBE_TIMEOUT) {
+                    if !strict || output.status.success() {
+                        if let Some(line) = first_nonempty_line(&output) {
+                            return line;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    "Not Found".to_string()
+}
+
+fn first_nonempty_line(output: &std::process::Output) -> Option<String> {
+    // Combine stdout and stderr because some tools print version to stderr.
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !stdout.is_empty() {
+        return Some(stdout.lines().next().unwrap_or("Unknown").trim().to_string());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if !stderr.is_empty() {
+        return Some(stderr.lines().next().unwrap_or("Unknown").trim().to_string());
+    }
+
+    None
+}
+
+fn check_uiautomator2(appium_cmd: &str) -> String {
+    // try --json first for better parsing
+    let mut command = Command::new(appium_cmd);
+    command.args(&["driver", "list", "--installed", "--json"]);
+
+    #[cfg(target_os = "windows")]
+    command.creation_flags(CREATE_NO_WINDOW);
+
+    // If direct fails, try shell wrapper logic again
+    let output_res = run_with_timeout(command, PROBE_TIMEOUT).or_else(|_| {
+        #[cfg(target_os = "windows")]
+        {
+            let mut shell_cmd = Command::new("cmd");
+            shell_cmd.creation_flags(CREATE_NO_WINDOW);
+            shell_cmd.args(&["/C", appium_cmd, "driver", "list", "--installed", "--json"]);
+            run_with_timeout(shell_cmd, PROBE_TIMEOUT)
+        }
+        #[cfg(not(target_os = "windows"))]
+        Err("not found".to_string())
+    });
+
+    if let Ok(output) = output_res {
+        if output.status.success() {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+
+            // Parse JSON
+            if let Ok(json) = serde_json::from_str::<Value>(&stdout) {
+                // Determine structure:
+                // Appium 2.x often returns: {"uiautomator2": {"version": "x.x.x", ...}}
+                if let Some(uia2) = json.get("uiautomator2") {
+                    if let Some(ver) = uia2.get("version") {
+                        return ver.as_str().unwrap_or("Installed").to_string();
+                    }
+                    return "Installed".to_string();
+                }
+            } else {
+                // Text mode fallback
+                if stdout.contains("uiautomator2") {
+                    if let Some(line) = stdout.lines().find(|l| l.contains("uiautomator2")) {
+                        return line.trim().to_string();
+                    }
+                    return "Installed".to_string();
+                }
+            }
+        }
+    }
+
+    "Not Found".to_string()
+}
+
+/// Run `command` with a hard wall-clock bound. Spawns the child, then moves it onto a
+/// worker thread that blocks on `wait_with_output()` and reports the result over an
+/// `mpsc` channel, while this function blocks on `rx.recv_timeout(timeout)` — the
+/// channel-plus-thread pattern used to bound blocking calls that have no native
+/// timeout. The child's pid is captured before the move so a timeout can still reach
+/// in and kill it even though the worker thread now owns the `Child` itself.
+fn run_with_timeout(mut command: Command, timeout: Duration) -> Result<std::process::Output, String> {
+    command.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped());
+
+    let child = command.spawn().map_err(|e| e.to_string())?;
+    let pid = child.id();
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = tx.send(child.wait_with_output());
+    });
+
+    match rx.recv_timeout(timeout) {
+        Ok(Ok(output)) => Ok(output),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            kill_pid(pid);
+            Err("Timed out".to_string())
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err("Probe worker vanished".to_string()),
+    }
+}
+
+fn kill_pid(pid: u32) {
+    #[cfg(target_os = "windows")]
+    {
+        let _ = Command::new("taskkill")
+            .args(&["/F", "/T", "/PID", &pid.to_string()])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output();
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        use nix::sys::signal::{self, Signal};
+        use nix::unistd::Pid;
+        let _ = signal::kill(Pid::from_raw(pid as i32), Signal::SIGKILL);
+    }
+}